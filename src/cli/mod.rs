@@ -0,0 +1,79 @@
+//! This module implements the subcommands of the `chess` binary.
+
+use std::fs::File;
+use std::io::BufReader;
+
+use crate::engine::book::builder::{BookBuilder, DEFAULT_MAX_PLY, DEFAULT_MIN_COUNT};
+
+#[cfg(test)]
+mod tests;
+
+/// The help message printed when the arguments are invalid.
+pub const USAGE: &str = "Usage:
+    chess book <games.pgn> <book.bin> [--max-ply <N>] [--min-count <N>]
+        Builds a Polyglot opening book from the games of a PGN file.";
+
+/// Runs the subcommand specified by the arguments, without the name of the binary.
+///
+/// Returns the message to report if the subcommand failed.
+pub fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("book") => book(&parse_book_args(&args[1..])?),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+/// The arguments of the `book` subcommand.
+#[derive(Debug, Eq, PartialEq)]
+struct BookArgs {
+    pgn: String,
+    output: String,
+    max_ply: usize,
+    min_count: u32,
+}
+
+fn parse_book_args(args: &[String]) -> Result<BookArgs, String> {
+    let mut paths = Vec::new();
+    let mut max_ply = DEFAULT_MAX_PLY;
+    let mut min_count = DEFAULT_MIN_COUNT;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-ply" => max_ply = parse_option(arg, args.next())?,
+            "--min-count" => min_count = parse_option(arg, args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option: {}\n{}", arg, USAGE)),
+            _ => paths.push(arg.clone()),
+        }
+    }
+
+    match paths.as_slice() {
+        [pgn, output] => Ok(BookArgs { pgn: pgn.clone(), output: output.clone(), max_ply, min_count }),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_option<T: std::str::FromStr>(name: &str, value: Option<&String>) -> Result<T, String> {
+    value.and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("Option {} requires a non-negative number\n{}", name, USAGE))
+}
+
+fn book(args: &BookArgs) -> Result<(), String> {
+    let pgn = File::open(&args.pgn)
+        .map_err(|error| format!("Unable to read {}: {}", args.pgn, error))?;
+
+    let mut builder = BookBuilder::new();
+    builder.set_max_ply(args.max_ply).set_min_count(args.min_count);
+    let import = builder.add_pgn_reader(BufReader::new(pgn))
+        .map_err(|error| format!("Unable to read {}: {}", args.pgn, error))?;
+    if let Some(error) = import.get_first_error() {
+        eprintln!("Skipped {} games which could not be read, the first one: {}", import.get_skipped(), error);
+    }
+
+    let book = builder.build();
+    book.save(&args.output)
+        .map_err(|error| format!("Unable to write {}: {}", args.output, error))?;
+
+    println!("Added {} games, wrote {} entries to {}", import.get_added(), book.len(), args.output);
+    Ok(())
+}
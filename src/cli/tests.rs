@@ -0,0 +1,37 @@
+use crate::cli::{BookArgs, parse_book_args, run};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn book_defaults() {
+    let parsed = parse_book_args(&args(&["games.pgn", "book.bin"])).unwrap();
+    assert_eq!(BookArgs {
+        pgn: "games.pgn".to_owned(),
+        output: "book.bin".to_owned(),
+        max_ply: 30,
+        min_count: 1,
+    }, parsed);
+}
+
+#[test]
+fn book_options() {
+    let parsed = parse_book_args(&args(&["--max-ply", "12", "games.pgn", "book.bin", "--min-count", "3"])).unwrap();
+    assert_eq!(12, parsed.max_ply);
+    assert_eq!(3, parsed.min_count);
+}
+
+#[test]
+fn book_invalid_args() {
+    assert!(parse_book_args(&args(&["games.pgn"])).is_err());
+    assert!(parse_book_args(&args(&["games.pgn", "book.bin", "--max-ply"])).is_err());
+    assert!(parse_book_args(&args(&["games.pgn", "book.bin", "--min-count", "-1"])).is_err());
+    assert!(parse_book_args(&args(&["games.pgn", "book.bin", "--depth", "3"])).is_err());
+}
+
+#[test]
+fn unknown_subcommand() {
+    assert!(run(&args(&[])).is_err());
+    assert!(run(&args(&["play"])).is_err());
+}
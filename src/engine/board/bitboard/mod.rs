@@ -68,16 +68,21 @@ impl BitBoard {
     /// X . . . . . . .
     /// ```
     pub fn format_with(&self, occupied: &str, empty: &str) -> String {
+        self.format_by(|_, is_occupied| if is_occupied { occupied } else { empty }.to_owned())
+    }
+
+    /// Formats the BitBoard as a chessboard, like `format_with()`, but the string of every square
+    /// is returned by `square_format`, which receives the square and whether its' bit is set.
+    ///
+    /// The lines are written from the 8th rank to the 1st, every line ends with a newline.
+    pub fn format_by<F: FnMut(Square, bool) -> String>(&self, mut square_format: F) -> String {
         let mut result: String = "".to_owned();
         let mut row: String = "".to_owned();
         for x in 0..64 {
-            if self.0 & (1u64 << x) == (1u64 << x) {
-                row.push_str(occupied);
-            } else {
-                row.push_str(empty);
-            }
+            let square = Square::new(x);
+            row.push_str(&square_format(square, self.is_set(square)));
             if x % 8 == 7 {
-                row.push_str("\n");
+                row.push('\n');
                 result.insert_str(0, &row);
                 row.clear();
             }
@@ -351,6 +356,22 @@ impl BitBoard {
     }
 }
 
+impl Iterator for BitBoard {
+    type Item = Square;
+
+    /// Returns the square of the Least Significant Bit and clears it, so a bitboard can be used
+    /// to iterate over every square that is set in it.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_empty() {
+            None
+        } else {
+            let square = Square::new(self.bit_scan_fw() as u8);
+            self.0 &= self.0 - 1;
+            Some(square)
+        }
+    }
+}
+
 impl BitAnd for BitBoard {
     type Output = BitBoard;
 
@@ -23,14 +23,14 @@ fn default() {
 fn eq() {
     let a = BitBoard::from(42);
     let b = BitBoard::from(42);
-    assert_eq!(true, a == b)
+    assert!(a == b)
 }
 
 #[test]
 fn no_eq() {
     let a = BitBoard::new();
     let b = BitBoard::from(42);
-    assert_eq!(true, a != b)
+    assert!(a != b)
 }
 
 #[test]
@@ -42,6 +42,7 @@ fn copy() {
 }
 
 #[test]
+#[allow(clippy::clone_on_copy)]
 fn clone() {
     let board = BitBoard::from(42);
     let other = board.clone();
@@ -58,31 +59,31 @@ fn empty() {
 #[test]
 fn universe() {
     let board = BitBoard::universe();
-    assert_eq!(std::u64::MAX, board.value())
+    assert_eq!(u64::MAX, board.value())
 }
 
 #[test]
 fn is_empty_true() {
     let board = BitBoard::empty();
-    assert_eq!(true, board.is_empty())
+    assert!(board.is_empty())
 }
 
 #[test]
 fn is_empty_false() {
     let board = BitBoard::universe();
-    assert_eq!(false, board.is_empty())
+    assert!(!board.is_empty())
 }
 
 #[test]
 fn is_not_empty_true() {
     let board = BitBoard::from(1u64);
-    assert_eq!(true, board.is_not_empty())
+    assert!(board.is_not_empty())
 }
 
 #[test]
 fn is_not_empty_false() {
     let board = BitBoard::empty();
-    assert_eq!(false, board.is_not_empty())
+    assert!(!board.is_not_empty())
 }
 
 #[test]
@@ -432,8 +433,8 @@ fn rotate_90_ccw_4_times() {
 #[test]
 fn is_set() {
     let board = BitBoard::from(0x0000000000000001);
-    assert_eq!(true, board.is_set(Square::from_pos(Rank::A, File::First)));
-    assert_eq!(false, board.is_set(Square::from_pos(Rank::A, File::Second)));
+    assert!(board.is_set(Square::from_pos(Rank::A, File::First)));
+    assert!(!board.is_set(Square::from_pos(Rank::A, File::Second)));
 }
 
 #[test]
@@ -447,3 +448,18 @@ fn toggle() {
     let board = BitBoard::from(0x0000000000000100);
     assert_eq!(BitBoard::empty().value(), board.toggle(Square::from_pos(Rank::B, File::First)).value());
 }
+
+#[test]
+fn iterate_empty() {
+    let board = BitBoard::empty();
+    assert_eq!(0, board.count());
+}
+
+#[test]
+fn iterate() {
+    let board = BitBoard::from(0x8000000000000081);
+    let squares: Vec<Square> = board.collect();
+    assert_eq!(vec![Square::from_pos(Rank::A, File::First),
+                    Square::from_pos(Rank::A, File::Eighth),
+                    Square::from_pos(Rank::H, File::Eighth)], squares);
+}
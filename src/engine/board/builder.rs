@@ -5,7 +5,8 @@ use crate::engine::board::square::Square;
 use crate::engine::board::piece::castling::CastlingRight;
 use crate::engine::board::piece::{color, Piece};
 use crate::engine::board::bitboard::BitBoard;
-use crate::engine::board::{piece, Board};
+use crate::engine::board::{piece, zobrist, Board};
+use crate::engine::board::validation::PositionError;
 
 pub struct BoardBuilder {
     turn: Color,
@@ -17,6 +18,12 @@ pub struct BoardBuilder {
     pieces: [BitBoard; piece::NUM_PIECES],
 }
 
+impl Default for BoardBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BoardBuilder {
     pub fn new() -> BoardBuilder {
         BoardBuilder {
@@ -80,6 +87,15 @@ impl BoardBuilder {
         board.castling_rights = self.castling_rights;
         board.colors = self.colors;
         board.pieces = self.pieces;
+        board.key = zobrist::hash(&board);
         board
     }
+
+    /// Builds the board and validates it, see `Board::validate()`.
+    ///
+    /// Returns every violated invariant if the position is not valid.
+    pub fn try_build(&self) -> Result<Board, Vec<PositionError>> {
+        let board = self.build();
+        board.validate().map(|_| board)
+    }
 }
@@ -2,6 +2,15 @@
 //!
 //! The move is represented by the *source* and the *destination* squares,
 //! and optionally a promotion.
+//!
+//! Moves can be converted to and from the long algebraic notation of the UCI protocol: the source
+//! and destination squares followed by the lowercase letter of the promoted piece, like "e2e4" or
+//! "e7e8q". Castling is written as the move of the king ("e1g1"), and "0000" is the null move.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 use crate::engine::board::piece::Piece;
 use crate::engine::board::square::Square;
@@ -9,6 +18,29 @@ use crate::engine::board::square::Square;
 #[cfg(test)]
 mod tests;
 
+/// The UCI notation of the null move.
+pub const NULL_MOVE: &str = "0000";
+
+/// Represents an error which happened while parsing a move in UCI notation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum UciMoveError {
+    /// The move is not written in UCI notation.
+    InvalidUci(String),
+    /// The move is not legal in the position.
+    IllegalMove(String),
+}
+
+impl Display for UciMoveError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            UciMoveError::InvalidUci(uci) => write!(f, "Invalid UCI move: {}", uci),
+            UciMoveError::IllegalMove(uci) => write!(f, "Illegal move: {}", uci),
+        }
+    }
+}
+
+impl Error for UciMoveError {}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct ChessMove {
     src: Square,
@@ -35,6 +67,18 @@ impl ChessMove {
         }
     }
 
+    /// Creates the null move, which passes the turn without moving a piece.
+    ///
+    /// The null move is represented by the same source and destination square.
+    pub fn null() -> ChessMove {
+        ChessMove::new(Square::default(), Square::default())
+    }
+
+    /// Checks whether this is the null move.
+    pub fn is_null(&self) -> bool {
+        *self == ChessMove::null()
+    }
+
     /// Returns the source square.
     pub fn get_source(&self) -> Square {
         self.src
@@ -50,3 +94,50 @@ impl ChessMove {
         self.promotion
     }
 }
+
+impl Display for ChessMove {
+    /// Writes the move in UCI notation, like "e2e4", "e7e8q" or "0000".
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        if self.is_null() {
+            return write!(f, "{}", NULL_MOVE);
+        }
+
+        write!(f, "{}{}", self.src, self.dst)?;
+        if let Some(promoted) = self.promotion {
+            write!(f, "{}", promoted.to_string().to_ascii_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ChessMove {
+    type Err = UciMoveError;
+
+    /// Parses a move in UCI notation, without checking whether it is legal in any position.
+    ///
+    /// Use `Board::parse_uci()` to validate the move against a position.
+    fn from_str(uci: &str) -> Result<Self, Self::Err> {
+        let invalid = || UciMoveError::InvalidUci(uci.to_owned());
+        if uci == NULL_MOVE {
+            return Ok(ChessMove::null());
+        }
+        if !uci.is_ascii() || (uci.len() != 4 && uci.len() != 5) {
+            return Err(invalid());
+        }
+
+        let src = Square::from_string(&uci[0..2]).ok_or_else(invalid)?;
+        let dst = Square::from_string(&uci[2..4]).ok_or_else(invalid)?;
+        if src == dst {
+            return Err(invalid());
+        }
+
+        match &uci[4..] {
+            "" => Ok(ChessMove::new(src, dst)),
+            "q" => Ok(ChessMove::new_promote(src, dst, Piece::Queen)),
+            "r" => Ok(ChessMove::new_promote(src, dst, Piece::Rook)),
+            "b" => Ok(ChessMove::new_promote(src, dst, Piece::Bishop)),
+            "n" => Ok(ChessMove::new_promote(src, dst, Piece::Knight)),
+            _ => Err(invalid()),
+        }
+    }
+}
@@ -47,3 +47,39 @@ fn get_promotion() {
     assert_ne!(None, chess_move.get_promotion());
     assert_eq!(Piece::Queen, chess_move.get_promotion().unwrap());
 }
+
+#[test]
+fn display_uci() {
+    let e2e4 = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth));
+    let e7e8q = ChessMove::new_promote(Square::from_pos(Rank::G, File::Fifth), Square::from_pos(Rank::H, File::Fifth), Piece::Queen);
+    assert_eq!("e2e4", e2e4.to_string());
+    assert_eq!("e7e8q", e7e8q.to_string());
+    assert_eq!("0000", ChessMove::null().to_string());
+}
+
+#[test]
+fn parse_uci() {
+    let e1g1 = ChessMove::new(Square::from_pos(Rank::A, File::Fifth), Square::from_pos(Rank::A, File::Seventh));
+    let a2a1n = ChessMove::new_promote(Square::from_pos(Rank::B, File::First), Square::from_pos(Rank::A, File::First), Piece::Knight);
+    assert_eq!(Ok(e1g1), "e1g1".parse());
+    assert_eq!(Ok(a2a1n), "a2a1n".parse());
+    assert_eq!(Ok(ChessMove::null()), "0000".parse());
+    assert!(ChessMove::null().is_null());
+    assert!(!e1g1.is_null());
+}
+
+#[test]
+fn parse_uci_invalid() {
+    use crate::engine::board::chessmove::UciMoveError;
+
+    for uci in ["", "e2", "e2e", "e2e4qq", "e2e9", "i2e4", "e2e2", "e7e8k", "e7e8Q", "é2e4"].iter() {
+        assert_eq!(Err(UciMoveError::InvalidUci(uci.to_string())), uci.parse::<ChessMove>());
+    }
+}
+
+#[test]
+fn uci_round_trip() {
+    for uci in ["a1h8", "h7h8r", "b2b1b", "g1f3"].iter() {
+        assert_eq!(*uci, uci.parse::<ChessMove>().unwrap().to_string());
+    }
+}
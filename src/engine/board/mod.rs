@@ -3,11 +3,18 @@
 use std::fmt::{Display, Error, Formatter};
 
 use crate::engine::board::bitboard::BitBoard;
-use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::chessmove::{ChessMove, UciMoveError};
 use crate::engine::board::piece::{ALL_PIECES, color, general, king, knight, pawn, Piece, sliding};
+use crate::engine::board::piece::castling;
 use crate::engine::board::piece::castling::CastlingRight;
 use crate::engine::board::piece::color::Color;
+use crate::engine::board::render::BoardRenderer;
+use crate::engine::board::svg::SvgRenderer;
+use crate::engine::board::san::SanError;
 use crate::engine::board::square::Square;
+use crate::engine::board::status::GameStatus;
+use crate::engine::board::validation::PositionError;
+use crate::engine::fen;
 
 mod constants;
 pub mod bitboard;
@@ -15,6 +22,14 @@ pub mod piece;
 pub mod square;
 pub mod chessmove;
 pub mod builder;
+pub mod movegen;
+pub mod validation;
+pub mod status;
+pub mod san;
+pub mod zobrist;
+pub mod render;
+pub mod svg;
+pub mod see;
 #[cfg(test)]
 mod tests;
 
@@ -25,6 +40,8 @@ mod tests;
 /// * En-passant target square
 /// * Castling rights for both players
 /// * Half- and Full move counters
+///
+/// The board also keeps the Zobrist hash of the position up to date, see `Board::get_key()`.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub struct Board {
     turn: Color,
@@ -34,6 +51,14 @@ pub struct Board {
     castling_rights: [CastlingRight; color::NUM_COLORS],
     colors: [BitBoard; color::NUM_COLORS],
     pieces: [BitBoard; piece::NUM_PIECES],
+    key: u64,
+}
+
+impl Default for Board {
+    /// Returns a board with every piece in it's initial position, see `Board::new()`.
+    fn default() -> Self {
+        Board::new()
+    }
 }
 
 impl Board {
@@ -41,7 +66,7 @@ impl Board {
     ///
     /// If you want an initial board instead, use `Board::new()` or `default()`.
     pub fn empty() -> Board {
-        Board {
+        let mut board = Board {
             turn: Color::White,
             half_moves: 0,
             full_moves: 0,
@@ -49,7 +74,10 @@ impl Board {
             castling_rights: [CastlingRight::NoRight, CastlingRight::NoRight],
             colors: [BitBoard::new(); color::NUM_COLORS],
             pieces: [BitBoard::new(); piece::NUM_PIECES],
-        }
+            key: 0,
+        };
+        board.key = zobrist::hash(&board);
+        board
     }
 
     /// Constructs a new board with every piece in it's initial position.
@@ -65,7 +93,7 @@ impl Board {
         colors[Color::White.to_index()] = Color::White.initial_position();
         colors[Color::Black.to_index()] = Color::Black.initial_position();
 
-        Board {
+        let mut board = Board {
             turn: Color::White,
             half_moves: 0,
             full_moves: 1,
@@ -73,32 +101,43 @@ impl Board {
             castling_rights: [CastlingRight::BothSide, CastlingRight::BothSide],
             colors,
             pieces,
-        }
+            key: 0,
+        };
+        board.key = zobrist::hash(&board);
+        board
     }
 
     /// Returns the color of the player who have to move.
     pub fn get_turn(&self) -> Color {
-        return self.turn;
+        self.turn
     }
 
-    /// Returns the number of half moves.
+    /// Returns the halfmove clock: the number of half moves since the last capture or pawn move.
     pub fn get_half_moves(&self) -> u16 {
-        return self.half_moves;
+        self.half_moves
     }
 
-    /// Returns the number of full moves.
+    /// Returns the fullmove number, which starts at 1 and is incremented after Black's move.
     pub fn get_full_moves(&self) -> u16 {
-        return self.full_moves;
+        self.full_moves
     }
 
     /// Returns en_passant target square, or `None`.
     pub fn get_en_passant(&self) -> Option<Square> {
-        return self.en_passant;
+        self.en_passant
     }
 
     /// Returns the castling rights.
     pub fn get_castling_rights(&self) -> [CastlingRight; 2] {
-        return self.castling_rights;
+        self.castling_rights
+    }
+
+    /// Returns the Zobrist hash of the position.
+    ///
+    /// The move counters are not part of the hash, so positions that are the same according to the
+    /// repetition rules have the same key.
+    pub fn get_key(&self) -> u64 {
+        self.key
     }
 
     /// Returns piece positions by piece type.
@@ -111,6 +150,111 @@ impl Board {
         self.pieces[piece.to_index()] & self.colors[color.to_index()]
     }
 
+    /// Returns the board as an SVG diagram, see `SvgRenderer` for highlights and arrows.
+    pub fn to_svg(&self) -> String {
+        SvgRenderer::new().render(self)
+    }
+
+    /// Returns the `Forsyth–Edwards Notation` of the board.
+    pub fn to_fen(&self) -> String {
+        fen::to_fen(self)
+    }
+
+    /// Validates the position, and returns every violated invariant.
+    ///
+    /// Checks that both players have exactly one king, there are no pawns on the first and last
+    /// ranks, the bitboards are consistent, the castling rights and the en passant target square
+    /// are possible, and the player who does not have to move is not in check.
+    pub fn validate(&self) -> Result<(), Vec<PositionError>> {
+        validation::validate(self)
+    }
+
+    /// Returns every legal move of the player who have to move.
+    pub fn legal_moves(&self) -> Vec<ChessMove> {
+        movegen::legal_moves(self)
+    }
+
+    /// Returns every pseudo-legal move of the player who have to move.
+    ///
+    /// Pseudo-legal moves follow the movement rules of the pieces, but may leave the own king in check.
+    pub fn pseudo_legal_moves(&self) -> Vec<ChessMove> {
+        movegen::pseudo_legal_moves(self)
+    }
+
+    /// Returns the legal captures and queen promotions of the player who have to move.
+    pub fn legal_captures(&self) -> Vec<ChessMove> {
+        movegen::legal_captures(self)
+    }
+
+    /// Returns the piece captured by the move, the captured pawn for en passant captures.
+    pub fn get_captured_piece(&self, chess_move: ChessMove) -> Option<Piece> {
+        let dst = chess_move.get_destination();
+        match self.piece_at(dst, !self.turn) {
+            Some(piece) => Some(piece),
+            None if (self.en_passant_capture() & dst.as_bb()).is_not_empty()
+                && self.get_pieces_color(Piece::Pawn, self.turn).is_set(chess_move.get_source()) => Some(Piece::Pawn),
+            None => None,
+        }
+    }
+
+    /// Returns the static exchange evaluation of the move: the material won in centipawns, if both
+    /// players continue capturing on the destination square as long as it is worth it.
+    pub fn see(&self, chess_move: ChessMove) -> i32 {
+        see::see(self, chess_move)
+    }
+
+    /// Checks whether the static exchange evaluation of the move is at least the `threshold`.
+    pub fn see_ge(&self, chess_move: ChessMove, threshold: i32) -> bool {
+        see::see_ge(self, chess_move, threshold)
+    }
+
+    /// Parses a move written in Standard Algebraic Notation, like `Nbd7`, `exd6` or `O-O-O+`.
+    ///
+    /// Returns the matching legal move, or an error if the move is invalid, illegal or ambiguous.
+    pub fn parse_san(&self, san: &str) -> Result<ChessMove, SanError> {
+        san::parse_san(self, san)
+    }
+
+    /// Writes a legal move in Standard Algebraic Notation, including the check or checkmate suffix.
+    pub fn to_san(&self, chess_move: ChessMove) -> String {
+        san::to_san(self, chess_move)
+    }
+
+    /// Parses a move written in UCI notation, like `e2e4`, `e7e8q` or `e1g1`.
+    ///
+    /// Returns the move if it is legal in the position. The null move (`0000`) is never legal,
+    /// because it cannot be made on the board.
+    pub fn parse_uci(&self, uci: &str) -> Result<ChessMove, UciMoveError> {
+        let chess_move: ChessMove = uci.parse()?;
+        if self.legal_moves().contains(&chess_move) {
+            Ok(chess_move)
+        } else {
+            Err(UciMoveError::IllegalMove(uci.to_owned()))
+        }
+    }
+
+    /// Returns the status of the game: whether it goes on, or ended with checkmate or a draw.
+    pub fn status(&self) -> GameStatus {
+        status::status(self)
+    }
+
+    /// Checks whether the two boards represent the same position according to the repetition rules.
+    ///
+    /// The move counters are ignored, and the en passant target square only matters if the player
+    /// who have to move has a pawn that could capture there.
+    pub fn is_same_position(&self, other: &Board) -> bool {
+        self.turn == other.turn
+            && self.castling_rights == other.castling_rights
+            && self.colors == other.colors
+            && self.pieces == other.pieces
+            && self.en_passant_capture() == other.en_passant_capture()
+    }
+
+    /// Checks whether the player who have to move is in check.
+    pub fn is_check(&self) -> bool {
+        self.in_check(self.turn)
+    }
+
     /// Validates the move and makes it, if it is valid.
     ///
     /// Returns the new board after the move, or `None` if the move was invalid.
@@ -159,12 +303,116 @@ impl Board {
             result.xor(captured, !self.turn, dst);
         }
 
-        result.half_moves += 1;
-        if result.turn == Color::Black {
+        // En passant captures the pawn which passed the target square
+        if piece == Piece::Pawn && (self.en_passant_target() & dst).is_not_empty() {
+            result.xor(Piece::Pawn, !self.turn, self.en_passant_victim());
+        }
+
+        // A double pawn push sets the en passant target square behind the pawn
+        let src_index = chess_move.get_source().to_index();
+        let dst_index = chess_move.get_destination().to_index();
+        result.en_passant = if piece == Piece::Pawn && (src_index + 16 == dst_index || dst_index + 16 == src_index) {
+            Some(Square::new(((src_index + dst_index) / 2) as u8))
+        } else {
+            None
+        };
+
+        // When castling, the rook jumps over the king
+        if piece == Piece::King {
+            if let Some(side) = castling::castling_side(self.turn, chess_move.get_source(), chess_move.get_destination()) {
+                let rook_move = castling::rook_square(self.turn, side).as_bb()
+                    | castling::rook_destination(self.turn, side).as_bb();
+                result.xor(Piece::Rook, self.turn, rook_move);
+            }
+        }
+
+        result.update_castling_rights(chess_move);
+        result.key ^= zobrist::castling_key(self.castling_rights) ^ zobrist::castling_key(result.castling_rights);
+
+        // The halfmove clock counts the moves since the last capture or pawn move (fifty-move rule)
+        let is_capture = (self.enemy_pieces() & dst).is_not_empty();
+        if piece == Piece::Pawn || is_capture {
+            result.half_moves = 0;
+        } else {
+            result.half_moves += 1;
+        }
+
+        // The fullmove number is incremented after Black's move
+        if self.turn == Color::Black {
             result.full_moves += 1;
         }
-        result.turn = !result.turn;
-        return result;
+        result.turn = !self.turn;
+        result.key ^= zobrist::side_key(self.turn) ^ zobrist::side_key(result.turn);
+        result.key ^= zobrist::en_passant_key(self) ^ zobrist::en_passant_key(&result);
+
+        debug_assert_eq!(zobrist::hash(&result), result.key, "incremental Zobrist key differs from full recomputation");
+        result
+    }
+
+    /// Removes the castling rights lost by the move.
+    ///
+    /// Moving the king loses both rights, while moving a rook from its' initial square or capturing
+    /// it there loses the right for that side.
+    fn update_castling_rights(&mut self, chess_move: ChessMove) {
+        for color in &color::ALL_COLORS {
+            let lost = castling::rights_lost(*color, chess_move.get_source())
+                .merge(castling::rights_lost(*color, chess_move.get_destination()));
+            self.castling_rights[color.to_index()] = self.castling_rights[color.to_index()].remove(lost);
+        }
+    }
+
+    /// Checks whether the player who have to move can castle to the specified `side`.
+    ///
+    /// The player must have the castling right, the squares between the king and the rook must be
+    /// empty, and the king cannot castle out of, through or into check.
+    fn can_castle(&self, side: CastlingRight) -> bool {
+        let color = self.turn;
+        self.castling_rights[color.to_index()].has(side)
+            && self.get_pieces_color(Piece::King, color).is_set(castling::king_square(color))
+            && self.get_pieces_color(Piece::Rook, color).is_set(castling::rook_square(color, side))
+            && (self.pieces() & castling::empty_path(color, side)).is_empty()
+            && castling::king_path(color, side).all(|square| !self.is_attacked(square, !color))
+    }
+
+    /// Returns the en passant target square as a bitboard, if the player who have to move can capture there.
+    ///
+    /// The bitboard is empty if there is no target square, or there is no enemy pawn behind it.
+    fn en_passant_target(&self) -> BitBoard {
+        match self.en_passant {
+            Some(target) => {
+                let victim = self.en_passant_victim_of(target) & self.get_pieces_color(Piece::Pawn, !self.turn);
+                if victim.is_not_empty() {
+                    target.as_bb()
+                } else {
+                    BitBoard::empty()
+                }
+            }
+            None => BitBoard::empty(),
+        }
+    }
+
+    /// Returns the en passant target square as a bitboard, if a pawn of the player who have to move attacks it.
+    fn en_passant_capture(&self) -> BitBoard {
+        let pawns = self.get_pieces_color(Piece::Pawn, self.turn);
+        pawn::any_valid_attack(self.turn, pawns, self.en_passant_target())
+    }
+
+    /// Returns the position of the enemy pawn that can be captured en passant.
+    fn en_passant_victim(&self) -> BitBoard {
+        self.en_passant.map_or(BitBoard::empty(), |target| self.en_passant_victim_of(target))
+    }
+
+    /// Returns the square of the pawn which passed the specified en passant target square.
+    fn en_passant_victim_of(&self, target: Square) -> BitBoard {
+        match self.turn {
+            Color::White => target.as_bb().south_one(),
+            Color::Black => target.as_bb().north_one(),
+        }
+    }
+
+    /// Checks whether any piece of the specified color attacks the square.
+    fn is_attacked(&self, square: Square, by: Color) -> bool {
+        (general::square_attacked_by(square, self) & self.pieces_by_color(by)).is_not_empty()
     }
 
     /// This function checks whether the king of the specified color is in check.
@@ -213,10 +461,11 @@ impl Board {
 
     /// Sets the specified piece and color in the specified positions.
     ///
-    /// Mutates the board. Does no sanity checking, so it can break the board!
+    /// Mutates the board, and updates the hash key. Does no sanity checking, so it can break the board!
     fn xor(&mut self, piece: Piece, color: Color, bb: BitBoard) {
         self.colors[color.to_index()] ^= bb;
         self.pieces[piece.to_index()] ^= bb;
+        self.key ^= zobrist::piece_key(piece, color, bb);
     }
 
     /// This function runs a sanity check before piece-wise move validation.
@@ -227,13 +476,19 @@ impl Board {
         let dst = chess_move.get_destination().as_bb();
         let promotion = chess_move.get_promotion();
 
-        let valid_promotion = promotion.map_or(true, |promoted| {
-            promoted != Piece::King // Cannot promote a king
+        let last_rank = match self.turn {
+            Color::White => BitBoard::from(0xFF00000000000000),
+            Color::Black => BitBoard::from(0x00000000000000FF),
+        };
+        let reaches_last_rank = (self.pieces_by_type(Piece::Pawn) & src).is_not_empty() && (last_rank & dst).is_not_empty();
+
+        // A pawn must promote exactly when it reaches the last rank
+        let valid_promotion = match promotion {
+            Some(promoted) => promoted != Piece::King // Cannot promote a king
                 && promoted != Piece::Pawn // Cannot promote a pawn
-                && (self.pieces_by_type(Piece::Pawn) & src).is_not_empty() // Must use a pawn for promotion
-                && (self.turn == Color::White && (BitBoard::from(0xFF00000000000000) & dst).is_not_empty()
-                || self.turn == Color::Black && (BitBoard::from(0x00000000000000FF) & dst).is_not_empty())
-        });
+                && reaches_last_rank,
+            None => !reaches_last_rank,
+        };
 
         src != dst // Source and destination must differ
             && (self.own_pieces() & src).is_not_empty() // Make sure we have a piece at the source square
@@ -247,13 +502,12 @@ impl Board {
         let piece = self.piece_at(chess_move.get_source(), self.turn).unwrap();
         match piece {
             Piece::Pawn => {
-                // TODO handle en-passant
                 let valid_moves = pawn::push_targets(self.turn,
                                                      chess_move.get_source().as_bb(),
                                                      self.empty_squares());
                 let valid_attacks = pawn::any_valid_attack(self.turn,
                                                            chess_move.get_source().as_bb(),
-                                                           self.enemy_pieces());
+                                                           self.enemy_pieces() | self.en_passant_target());
 
                 ((valid_moves | valid_attacks) & chess_move.get_destination().as_bb()).is_not_empty()
             }
@@ -265,6 +519,10 @@ impl Board {
                 (valid_moves & chess_move.get_destination().as_bb()).is_not_empty()
             }
             Piece::King => {
+                if let Some(side) = castling::castling_side(self.turn, chess_move.get_source(), chess_move.get_destination()) {
+                    return self.can_castle(side);
+                }
+
                 let attack_targets = king::attack_targets(chess_move.get_source().as_bb());
                 let valid_moves = attack_targets & !self.own_pieces();
                 // Check if there are anyone attacking the destination
@@ -301,13 +559,8 @@ impl Board {
 }
 
 impl Display for Board {
+    /// Writes the pieces with the rank and file labels and the side to move, see `BoardRenderer`.
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        let mut board = BitBoard::new();
-        for color in &color::ALL_COLORS {
-            board = board | self.colors[color.to_index()];
-        }
-
-
-        write!(f, "{}", board)
+        write!(f, "{}", BoardRenderer::new().render(self))
     }
 }
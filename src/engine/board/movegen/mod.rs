@@ -0,0 +1,153 @@
+//! This module implements move generation for a `Board`.
+//!
+//! Moves are first generated *pseudo-legally*, meaning that every piece moves according to its'
+//! movement rules, but the moves may leave the own king in check. The legal moves are the
+//! pseudo-legal moves after which the king of the moving side is not in check.
+//!
+//! The quiescence search only needs the captures and the queen promotions, they are generated
+//! separately, without generating the quiet moves.
+
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::{castling, general, king, knight, pawn, Piece, PROMOTION_PIECES, sliding};
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::square::constants::{RANK_A, RANK_H};
+
+#[cfg(test)]
+mod tests;
+
+/// The piece types (except the pawn) that move to the squares they attack.
+const NON_PAWN_PIECES: [Piece; 5] = [
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King
+];
+
+/// Generates every pseudo-legal move of the side to move.
+///
+/// The moves follow the movement rules of the pieces, but they may leave the own king in check.
+pub fn pseudo_legal_moves(board: &Board) -> Vec<ChessMove> {
+    let mut moves = Vec::new();
+    // Kings can never be captured
+    let targets = !board.own_pieces() & !board.pieces_by_type(Piece::King);
+
+    generate_pawn_moves(board, targets, &mut moves);
+    generate_piece_moves(board, targets, &mut moves);
+    generate_castling_moves(board, &mut moves);
+    moves
+}
+
+/// Generates every legal move of the side to move.
+pub fn legal_moves(board: &Board) -> Vec<ChessMove> {
+    pseudo_legal_moves(board)
+        .into_iter()
+        .filter(|chess_move| is_king_safe_after(board, *chess_move))
+        .collect()
+}
+
+/// Generates the pseudo-legal captures and queen promotions of the side to move.
+///
+/// Promotions, with or without capture, are only generated to queen.
+pub fn pseudo_legal_captures(board: &Board) -> Vec<ChessMove> {
+    let mut moves = Vec::new();
+    let color = board.turn;
+    let occupied = board.pieces();
+    // Kings can never be captured
+    let victims = board.enemy_pieces() & !board.pieces_by_type(Piece::King);
+    let promotion_rank = match color {
+        Color::White => RANK_H,
+        Color::Black => RANK_A,
+    };
+
+    for src in board.get_pieces_color(Piece::Pawn, color) {
+        let captures = pawn::any_valid_attack(color, src.as_bb(), victims | board.en_passant_target());
+        let promotions = pawn::single_push_targets(color, src.as_bb(), board.empty_squares()) & promotion_rank;
+        for dst in captures | promotions {
+            if promotion_rank.is_set(dst) {
+                moves.push(ChessMove::new_promote(src, dst, Piece::Queen));
+            } else {
+                moves.push(ChessMove::new(src, dst));
+            }
+        }
+    }
+
+    for piece in &NON_PAWN_PIECES {
+        for src in board.get_pieces_color(*piece, color) {
+            let attacks = match piece {
+                Piece::Knight => knight::attack_targets(src.as_bb()),
+                Piece::King => king::attack_targets(src.as_bb()),
+                _ => sliding::get_piece_attacks(*piece, src, occupied),
+            };
+            for dst in attacks & victims {
+                moves.push(ChessMove::new(src, dst));
+            }
+        }
+    }
+
+    moves
+}
+
+/// Generates the legal captures and queen promotions of the side to move.
+pub fn legal_captures(board: &Board) -> Vec<ChessMove> {
+    pseudo_legal_captures(board)
+        .into_iter()
+        .filter(|chess_move| is_king_safe_after(board, *chess_move))
+        .collect()
+}
+
+/// Checks whether the king of the moving side is out of check after making the pseudo-legal move.
+fn is_king_safe_after(board: &Board, chess_move: ChessMove) -> bool {
+    !board.make_move_without_validation(chess_move).in_check(board.turn)
+}
+
+/// Generates the pushes and captures of the pawns, including promotions and en passant captures.
+fn generate_pawn_moves(board: &Board, targets: BitBoard, moves: &mut Vec<ChessMove>) {
+    let color = board.turn;
+    let promotion_rank = match color {
+        Color::White => RANK_H,
+        Color::Black => RANK_A,
+    };
+
+    for src in board.get_pieces_color(Piece::Pawn, color) {
+        let pushes = pawn::push_targets(color, src.as_bb(), board.empty_squares());
+        let attacks = pawn::any_valid_attack(color, src.as_bb(),
+                                             (board.enemy_pieces() & targets) | board.en_passant_target());
+
+        for dst in pushes | attacks {
+            if promotion_rank.is_set(dst) {
+                for promoted in &PROMOTION_PIECES {
+                    moves.push(ChessMove::new_promote(src, dst, *promoted));
+                }
+            } else {
+                moves.push(ChessMove::new(src, dst));
+            }
+        }
+    }
+}
+
+/// Generates the moves of every piece except the pawns.
+fn generate_piece_moves(board: &Board, targets: BitBoard, moves: &mut Vec<ChessMove>) {
+    let occupied = board.pieces();
+
+    for piece in &NON_PAWN_PIECES {
+        for src in board.get_pieces_color(*piece, board.turn) {
+            let attacks = general::attack_targets(*piece, board.turn, src, occupied);
+            for dst in attacks & targets {
+                moves.push(ChessMove::new(src, dst));
+            }
+        }
+    }
+}
+
+/// Generates the castling moves, represented by the king moving two squares towards the rook.
+fn generate_castling_moves(board: &Board, moves: &mut Vec<ChessMove>) {
+    for side in &castling::CASTLING_SIDES {
+        if board.can_castle(*side) {
+            moves.push(ChessMove::new(castling::king_square(board.turn),
+                                      castling::king_destination(board.turn, *side)));
+        }
+    }
+}
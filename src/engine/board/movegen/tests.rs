@@ -0,0 +1,128 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::fen;
+
+#[test]
+fn legal_moves_initial() {
+    let board = Board::new();
+    assert_eq!(20, board.legal_moves().len());
+}
+
+#[test]
+fn legal_moves_initial_black() {
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(20, board.legal_moves().len());
+}
+
+#[test]
+fn legal_moves_are_accepted_by_make_move() {
+    let board = fen::from_fen("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1").unwrap();
+    let moves = board.legal_moves();
+    assert_eq!(14, moves.len());
+    for chess_move in moves {
+        assert!(board.make_move(chess_move).is_some());
+    }
+}
+
+#[test]
+fn legal_moves_promotions_and_check() {
+    let board = fen::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1").unwrap();
+    assert_eq!(6, board.legal_moves().len());
+}
+
+#[test]
+fn legal_moves_promotion() {
+    let board = fen::from_fen("7k/P7/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let moves = board.legal_moves();
+    let src = Square::from_pos(Rank::G, File::First);
+    let dst = Square::from_pos(Rank::H, File::First);
+
+    assert_eq!(7, moves.len());
+    assert!(moves.contains(&ChessMove::new_promote(src, dst, Piece::Queen)));
+    assert!(moves.contains(&ChessMove::new_promote(src, dst, Piece::Rook)));
+    assert!(moves.contains(&ChessMove::new_promote(src, dst, Piece::Bishop)));
+    assert!(moves.contains(&ChessMove::new_promote(src, dst, Piece::Knight)));
+    assert!(!moves.contains(&ChessMove::new(src, dst)));
+}
+
+#[test]
+fn legal_moves_pinned_piece() {
+    // The knight on e2 is pinned by the rook on e8
+    let board = fen::from_fen("4r2k/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+    let knight = Square::from_pos(Rank::B, File::Fifth);
+
+    assert_eq!(6, board.pseudo_legal_moves().iter().filter(|m| m.get_source() == knight).count());
+    assert_eq!(0, board.legal_moves().iter().filter(|m| m.get_source() == knight).count());
+}
+
+#[test]
+fn legal_moves_checkmate() {
+    let board = fen::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+    assert!(board.legal_moves().is_empty());
+}
+
+#[test]
+fn pseudo_legal_moves_no_king_capture() {
+    let board = fen::from_fen("k7/8/8/8/8/8/8/R6K w - - 0 1").unwrap();
+    let king = Square::from_pos(Rank::H, File::First);
+    assert!(board.pseudo_legal_moves().iter().all(|m| m.get_destination() != king));
+}
+
+#[test]
+fn legal_moves_castling() {
+    let board = fen::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let moves = board.legal_moves();
+    let king = Square::from_pos(Rank::A, File::Fifth);
+
+    assert_eq!(48, moves.len());
+    assert!(moves.contains(&ChessMove::new(king, Square::from_pos(Rank::A, File::Seventh))));
+    assert!(moves.contains(&ChessMove::new(king, Square::from_pos(Rank::A, File::Third))));
+}
+
+#[test]
+fn legal_moves_castling_one_side() {
+    let board = fen::from_fen("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8").unwrap();
+    assert_eq!(44, board.legal_moves().len());
+}
+
+#[test]
+fn legal_moves_en_passant() {
+    let board = fen::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let moves = board.legal_moves();
+    let src = Square::from_pos(Rank::E, File::Fifth);
+
+    assert!(moves.contains(&ChessMove::new(src, Square::from_pos(Rank::F, File::Sixth))));
+    assert!(!moves.contains(&ChessMove::new(src, Square::from_pos(Rank::F, File::Fourth))));
+    assert_eq!(31, moves.len());
+}
+
+#[test]
+fn legal_captures() {
+    let board = fen::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let captures = board.legal_captures();
+    let expected: Vec<_> = board.legal_moves()
+        .into_iter()
+        .filter(|chess_move| board.get_captured_piece(*chess_move).is_some())
+        .collect();
+
+    assert_eq!(8, captures.len());
+    assert_eq!(expected.len(), captures.len());
+    assert!(expected.iter().all(|chess_move| captures.contains(chess_move)));
+}
+
+#[test]
+fn legal_captures_promotions_and_en_passant() {
+    let board = fen::from_fen("1n2k3/P7/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    let captures = board.legal_captures();
+
+    assert_eq!(3, captures.len());
+    assert!(captures.contains(&ChessMove::new_promote(Square::from_pos(Rank::G, File::First),
+                                                      Square::from_pos(Rank::H, File::First), Piece::Queen)));
+    assert!(captures.contains(&ChessMove::new_promote(Square::from_pos(Rank::G, File::First),
+                                                      Square::from_pos(Rank::H, File::Second), Piece::Queen)));
+    let en_passant = ChessMove::new(Square::from_pos(Rank::E, File::Fifth), Square::from_pos(Rank::F, File::Fourth));
+    assert!(captures.contains(&en_passant));
+    assert_eq!(Some(Piece::Pawn), board.get_captured_piece(en_passant));
+}
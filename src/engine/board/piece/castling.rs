@@ -1,5 +1,9 @@
 //! This module implements Castling Right related logic.
 
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::square::{File, Rank, Square};
+
 /// This enum represents the castling right of the player.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum CastlingRight {
@@ -13,6 +17,9 @@ pub enum CastlingRight {
     NoRight,
 }
 
+/// The two sides a player can castle to.
+pub const CASTLING_SIDES: [CastlingRight; 2] = [CastlingRight::KingSide, CastlingRight::QueenSide];
+
 impl CastlingRight {
     /// Merges castling rights.
     ///
@@ -38,4 +45,133 @@ impl CastlingRight {
             }
         }
     }
+
+    /// Removes the `other` castling rights from these rights.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(CastlingRight::QueenSide, CastlingRight::BothSide.remove(CastlingRight.KingSide));
+    /// ```
+    pub fn remove(&self, other: CastlingRight) -> CastlingRight {
+        let king_side = self.has_king_side() && !other.has_king_side();
+        let queen_side = self.has_queen_side() && !other.has_queen_side();
+        match (king_side, queen_side) {
+            (true, true) => CastlingRight::BothSide,
+            (true, false) => CastlingRight::KingSide,
+            (false, true) => CastlingRight::QueenSide,
+            (false, false) => CastlingRight::NoRight,
+        }
+    }
+
+    /// Checks whether king side castling is allowed.
+    pub fn has_king_side(&self) -> bool {
+        *self == CastlingRight::KingSide || *self == CastlingRight::BothSide
+    }
+
+    /// Checks whether queen side castling is allowed.
+    pub fn has_queen_side(&self) -> bool {
+        *self == CastlingRight::QueenSide || *self == CastlingRight::BothSide
+    }
+
+    /// Checks whether every castling right of `side` is allowed.
+    pub fn has(&self, side: CastlingRight) -> bool {
+        match side {
+            CastlingRight::KingSide => self.has_king_side(),
+            CastlingRight::QueenSide => self.has_queen_side(),
+            CastlingRight::BothSide => self.has_king_side() && self.has_queen_side(),
+            CastlingRight::NoRight => true,
+        }
+    }
+}
+
+/// Returns the rank on which the pieces of the specified color start the game.
+fn back_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::A,
+        Color::Black => Rank::H,
+    }
+}
+
+/// Shifts a first rank bitboard to the back rank of the specified color.
+fn on_back_rank(color: Color, first_rank: u64) -> BitBoard {
+    match color {
+        Color::White => BitBoard::from(first_rank),
+        Color::Black => BitBoard::from(first_rank << 56),
+    }
+}
+
+/// Returns the initial square of the king of the specified color.
+pub fn king_square(color: Color) -> Square {
+    Square::from_pos(back_rank(color), File::Fifth)
+}
+
+/// Returns the initial square of the rook used for castling to the specified `side`.
+pub fn rook_square(color: Color, side: CastlingRight) -> Square {
+    match side {
+        CastlingRight::QueenSide => Square::from_pos(back_rank(color), File::First),
+        _ => Square::from_pos(back_rank(color), File::Eighth),
+    }
+}
+
+/// Returns the square where the king arrives after castling to the specified `side`.
+pub fn king_destination(color: Color, side: CastlingRight) -> Square {
+    match side {
+        CastlingRight::QueenSide => Square::from_pos(back_rank(color), File::Third),
+        _ => Square::from_pos(back_rank(color), File::Seventh),
+    }
+}
+
+/// Returns the square where the rook arrives after castling to the specified `side`.
+pub fn rook_destination(color: Color, side: CastlingRight) -> Square {
+    match side {
+        CastlingRight::QueenSide => Square::from_pos(back_rank(color), File::Fourth),
+        _ => Square::from_pos(back_rank(color), File::Sixth),
+    }
+}
+
+/// Returns the squares between the king and the rook, which must be empty for castling.
+pub fn empty_path(color: Color, side: CastlingRight) -> BitBoard {
+    match side {
+        CastlingRight::QueenSide => on_back_rank(color, 0x0E),
+        _ => on_back_rank(color, 0x60),
+    }
+}
+
+/// Returns the squares the king stands on, passes through and arrives to while castling.
+///
+/// None of these squares can be attacked by the enemy.
+pub fn king_path(color: Color, side: CastlingRight) -> BitBoard {
+    match side {
+        CastlingRight::QueenSide => on_back_rank(color, 0x1C),
+        _ => on_back_rank(color, 0x70),
+    }
+}
+
+/// Determines the castling side of a king move from `src` to `dst`.
+///
+/// Returns `None` if the move is not a castling move.
+pub fn castling_side(color: Color, src: Square, dst: Square) -> Option<CastlingRight> {
+    if src != king_square(color) {
+        None
+    } else if dst == king_destination(color, CastlingRight::KingSide) {
+        Some(CastlingRight::KingSide)
+    } else if dst == king_destination(color, CastlingRight::QueenSide) {
+        Some(CastlingRight::QueenSide)
+    } else {
+        None
+    }
+}
+
+/// Returns the castling rights lost by the specified color when a piece moves from or to `square`.
+pub fn rights_lost(color: Color, square: Square) -> CastlingRight {
+    if square == king_square(color) {
+        CastlingRight::BothSide
+    } else if square == rook_square(color, CastlingRight::KingSide) {
+        CastlingRight::KingSide
+    } else if square == rook_square(color, CastlingRight::QueenSide) {
+        CastlingRight::QueenSide
+    } else {
+        CastlingRight::NoRight
+    }
 }
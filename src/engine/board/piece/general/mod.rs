@@ -16,6 +16,25 @@ pub fn square_attacked_by(square: Square, board: &Board) -> BitBoard {
     square_attacked_by_helper(square, board.pieces, board.colors, board.pieces())
 }
 
+/// Returns every piece attacking a particular square, considering only the `occupied` squares.
+///
+/// The pieces which are not on the `occupied` squares are ignored, and the sliding pieces behind
+/// them are found, so captured pieces can be removed one by one.
+pub fn square_attacked_by_occupied(square: Square, board: &Board, occupied: BitBoard) -> BitBoard {
+    square_attacked_by_helper(square, board.pieces, board.colors, occupied) & occupied
+}
+
+/// Returns the squares attacked by a single `piece` of the specified `color` standing on `square`,
+/// considering the `occupied` squares.
+pub fn attack_targets(piece: Piece, color: Color, square: Square, occupied: BitBoard) -> BitBoard {
+    match piece {
+        Piece::Pawn => pawn::any_attack(color, square.as_bb()),
+        Piece::Knight => knight::attack_targets(square.as_bb()),
+        Piece::King => king::attack_targets(square.as_bb()),
+        _ => sliding::get_piece_attacks(piece, square, occupied),
+    }
+}
+
 fn square_attacked_by_helper(square: Square,
                           pieces: [BitBoard; piece::NUM_PIECES],
                           colors: [BitBoard; color::NUM_COLORS],
@@ -23,14 +42,14 @@ fn square_attacked_by_helper(square: Square,
     let black_pawns = pieces[Piece::Pawn.to_index()] & colors[Color::Black.to_index()];
     let white_pawns = pieces[Piece::Pawn.to_index()] & colors[Color::White.to_index()];
     let knights = pieces[Piece::Knight.to_index()];
-    let sliding_pieces = pieces[Piece::Queen.to_index()]
-        | pieces[Piece::Rook.to_index()]
-        | pieces[Piece::Bishop.to_index()];
+    let diagonal_pieces = pieces[Piece::Queen.to_index()] | pieces[Piece::Bishop.to_index()];
+    let orthogonal_pieces = pieces[Piece::Queen.to_index()] | pieces[Piece::Rook.to_index()];
     let kings = pieces[Piece::King.to_index()];
 
-    return (pawn::any_valid_attack(Color::White, square.as_bb(), occupied ^ white_pawns) & black_pawns)
+    (pawn::any_valid_attack(Color::White, square.as_bb(), occupied ^ white_pawns) & black_pawns)
         | (pawn::any_valid_attack(Color::Black, square.as_bb(), occupied ^ black_pawns) & white_pawns)
         | (knight::attack_targets(square.as_bb()) & knights)
-        | (sliding::get_piece_attacks(Piece::Queen, square, occupied) & sliding_pieces)
-        | (king::attack_targets(square.as_bb()) & kings);
+        | (sliding::bishop_attacks(square, occupied) & diagonal_pieces)
+        | (sliding::rook_attacks(square, occupied) & orthogonal_pieces)
+        | (king::attack_targets(square.as_bb()) & kings)
 }
@@ -1,9 +1,11 @@
 use crate::engine::board::bitboard::BitBoard;
 use crate::engine::board::Board;
 use crate::engine::board::piece::color::Color;
-use crate::engine::board::piece::general::square_attacked_by;
+use crate::engine::board::piece::general;
+use crate::engine::board::piece::general::{attack_targets, square_attacked_by};
 use crate::engine::board::piece::Piece;
 use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::fen;
 
 // Test with an empty board
 #[test]
@@ -177,3 +179,56 @@ fn square_attacked_by_pawns() {
     let attackers = square_attacked_by(square, &board);
     assert_eq!(BitBoard::from(0x0000140014000000), attackers);
 }
+
+#[test]
+fn attack_targets_knight() {
+    let square = Square::from_pos(Rank::A, File::First);
+    let attacks = attack_targets(Piece::Knight, Color::White, square, BitBoard::empty());
+    assert_eq!(BitBoard::from(0x0000000000020400), attacks);
+}
+
+#[test]
+fn attack_targets_pawn() {
+    let square = Square::from_pos(Rank::B, File::Second);
+    assert_eq!(BitBoard::from(0x0000000000050000),
+               attack_targets(Piece::Pawn, Color::White, square, BitBoard::empty()));
+    assert_eq!(BitBoard::from(0x0000000000000005),
+               attack_targets(Piece::Pawn, Color::Black, square, BitBoard::empty()));
+}
+
+#[test]
+fn attack_targets_rook_blocked() {
+    let square = Square::from_pos(Rank::A, File::First);
+    let occupied = Square::from_pos(Rank::C, File::First).as_bb();
+    let attacks = attack_targets(Piece::Rook, Color::White, square, occupied);
+    assert_eq!(BitBoard::from(0x00000000000101FE), attacks);
+}
+
+// A rook on the diagonal and a bishop on the rank do not attack the square
+#[test]
+fn square_attacked_by_wrong_direction() {
+    let mut board = Board::empty();
+    board.xor(Piece::Rook,
+              Color::Black,
+              Square::from_pos(Rank::D, File::Fourth).as_bb());
+    board.xor(Piece::Bishop,
+              Color::Black,
+              Square::from_pos(Rank::A, File::Fourth).as_bb());
+
+    let square = Square::from_pos(Rank::A, File::First);
+    let attackers = square_attacked_by(square, &board);
+    assert_eq!(BitBoard::empty(), attackers);
+}
+
+#[test]
+fn square_attacked_by_occupied() {
+    let board = fen::from_fen("4k3/4r3/8/8/8/8/4R3/4R1K1 w - - 0 1").unwrap();
+    let square = Square::from_string("e7").unwrap();
+    let attackers = general::square_attacked_by_occupied(square, &board, board.pieces());
+    assert_eq!(Square::from_string("e2").unwrap().as_bb() | Square::from_string("e8").unwrap().as_bb(), attackers);
+
+    // The rook behind the removed one attacks through it
+    let occupied = board.pieces() ^ Square::from_string("e2").unwrap().as_bb();
+    let attackers = general::square_attacked_by_occupied(square, &board, occupied);
+    assert_eq!(Square::from_string("e1").unwrap().as_bb() | Square::from_string("e8").unwrap().as_bb(), attackers);
+}
@@ -22,12 +22,12 @@ mod tests;
 /// . . . . . . . .
 /// ```
 pub fn attack_targets(kings: BitBoard) -> BitBoard {
-    return kings.east_one()
+    kings.east_one()
         | kings.west_one()
         | kings.north_one()
         | kings.south_one()
         | kings.no_ea_one()
         | kings.no_we_one()
         | kings.so_ea_one()
-        | kings.so_we_one();
+        | kings.so_we_one()
 }
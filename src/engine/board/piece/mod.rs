@@ -4,6 +4,7 @@
 use std::fmt::{Display, Error, Formatter};
 
 use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::piece::color::Color;
 
 pub mod general;
 pub mod sliding;
@@ -40,6 +41,14 @@ pub const ALL_PIECES: [Piece; NUM_PIECES] = [
     Piece::Queen
 ];
 
+/// The piece types a pawn can be promoted to, in order of decreasing value.
+pub const PROMOTION_PIECES: [Piece; 4] = [
+    Piece::Queen,
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Knight
+];
+
 impl Display for Piece {
     /// Converts the piece type to a string representation.
     ///
@@ -70,6 +79,38 @@ impl Piece {
         *self as usize
     }
 
+    /// Returns the material value of the piece in centipawns.
+    ///
+    /// The king cannot be exchanged, so its' value is larger than all the other pieces together.
+    pub fn get_value(&self) -> i32 {
+        match *self {
+            Piece::Pawn => 100,
+            Piece::Knight => 320,
+            Piece::Bishop => 330,
+            Piece::Rook => 500,
+            Piece::Queen => 900,
+            Piece::King => 20000,
+        }
+    }
+
+    /// Returns the Unicode chess symbol of the piece of the color, like `♔` and `♚`.
+    pub fn to_figurine(&self, color: Color) -> char {
+        match (*self, color) {
+            (Piece::King, Color::White) => '♔',
+            (Piece::Queen, Color::White) => '♕',
+            (Piece::Rook, Color::White) => '♖',
+            (Piece::Bishop, Color::White) => '♗',
+            (Piece::Knight, Color::White) => '♘',
+            (Piece::Pawn, Color::White) => '♙',
+            (Piece::King, Color::Black) => '♚',
+            (Piece::Queen, Color::Black) => '♛',
+            (Piece::Rook, Color::Black) => '♜',
+            (Piece::Bishop, Color::Black) => '♝',
+            (Piece::Knight, Color::Black) => '♞',
+            (Piece::Pawn, Color::Black) => '♟',
+        }
+    }
+
     /// Returns a bitboard with the initial positions for the current piece.
     pub fn initial_position(&self) -> BitBoard {
         match *self {
@@ -62,7 +62,7 @@ fn anti_diagonal_mask(square: Square) -> BitBoard {
 }
 
 fn diagonal_attacks(square: Square, occupied: BitBoard) -> BitBoard {
-    let mut forward = occupied & diagonal_mask(square);
+    let mut forward = occupied & (diagonal_mask(square) ^ square.as_bb());
     let mut reverse = forward.flip_vertical();
     forward.0 = forward.0.wrapping_sub(square.as_bb().0);
     reverse.0 = reverse.0.wrapping_sub(square.as_bb().flip_vertical().0);
@@ -72,7 +72,7 @@ fn diagonal_attacks(square: Square, occupied: BitBoard) -> BitBoard {
 }
 
 fn anti_diagonal_attacks(square: Square, occupied: BitBoard) -> BitBoard {
-    let mut forward = occupied & anti_diagonal_mask(square);
+    let mut forward = occupied & (anti_diagonal_mask(square) ^ square.as_bb());
     let mut reverse = forward.flip_vertical();
     forward.0 = forward.0.wrapping_sub(square.as_bb().0);
     reverse.0 = reverse.0.wrapping_sub(square.as_bb().flip_vertical().0);
@@ -82,7 +82,7 @@ fn anti_diagonal_attacks(square: Square, occupied: BitBoard) -> BitBoard {
 }
 
 fn file_attacks(square: Square, occupied: BitBoard) -> BitBoard {
-    let mut forward = occupied & file_mask(square);
+    let mut forward = occupied & (file_mask(square) ^ square.as_bb());
     let mut reverse = forward.flip_vertical();
     forward.0 = forward.0.wrapping_sub(square.as_bb().0);
     reverse.0 = reverse.0.wrapping_sub(square.as_bb().flip_vertical().0);
@@ -105,9 +105,9 @@ lazy_static! {
 fn init_first_rank_attacks() -> [[u8; 256]; 8] {
     let mut result: [[u8; 256]; 8] = [[0; 256]; 8];
 
-    for file in 0..8 {
-        for occupancy in 0..256 {
-            result[file][occupancy] = single_rank_attacks(1u8 << file, occupancy as u8);
+    for (file, attacks) in result.iter_mut().enumerate() {
+        for (occupancy, attack) in attacks.iter_mut().enumerate() {
+            *attack = single_rank_attacks(1u8 << file, occupancy as u8);
         }
     }
 
@@ -133,7 +133,7 @@ fn left_rank_attacks(file: u8, occ: u8) -> u8 {
             break;
         }
 
-        next = next << 1;
+        next <<= 1;
     }
 
     result
@@ -153,7 +153,7 @@ fn right_rank_attacks(file: u8, occ: u8) -> u8 {
             break;
         }
 
-        next = next >> 1;
+        next >>= 1;
     }
 
     result
@@ -168,5 +168,5 @@ fn rank_attacks(sq: Square, occ: BitBoard) -> BitBoard {
 
     // Search for the attack targets in the lookup table
     let attacks = FIRST_RANK_ATTACKS[file as usize][rank_occurences as usize] as u64;
-    BitBoard(attacks << rankx8 as usize)
+    BitBoard(attacks << rankx8)
 }
@@ -328,3 +328,11 @@ fn queen_middle_occupied() {
 
     assert_eq!(expected, attacks);
 }
+
+// The slider's own square may be part of the occupancy
+#[test]
+fn queen_attacks_own_square_occupied() {
+    let square = Square::from_pos(Rank::D, File::Fourth);
+    let expected = queen_attacks(square, BitBoard::empty());
+    assert_eq!(expected, queen_attacks(square, square.as_bb()));
+}
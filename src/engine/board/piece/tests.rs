@@ -23,6 +23,7 @@ mod color {
 
 #[cfg(test)]
 mod piece {
+    use crate::engine::board::piece::color::Color;
     use crate::engine::board::piece::Piece;
 
     #[test]
@@ -34,6 +35,22 @@ mod piece {
         assert_eq!(4, Piece::King.to_index());
         assert_eq!(5, Piece::Queen.to_index());
     }
+
+    #[test]
+    fn get_value() {
+        assert_eq!(100, Piece::Pawn.get_value());
+        assert!(Piece::Knight.get_value() < Piece::Bishop.get_value());
+        assert!(Piece::Rook.get_value() < Piece::Queen.get_value());
+        assert!(Piece::King.get_value() > 2 * Piece::Queen.get_value() + 8 * Piece::Pawn.get_value());
+    }
+
+    #[test]
+    fn to_figurine() {
+        assert_eq!('♔', Piece::King.to_figurine(Color::White));
+        assert_eq!('♙', Piece::Pawn.to_figurine(Color::White));
+        assert_eq!('♛', Piece::Queen.to_figurine(Color::Black));
+        assert_eq!('♞', Piece::Knight.to_figurine(Color::Black));
+    }
 }
 
 #[cfg(test)]
@@ -97,4 +114,36 @@ mod castling_right {
         assert_eq!(CastlingRight::QueenSide, CastlingRight::QueenSide.merge(CastlingRight::NoRight));
         assert_eq!(CastlingRight::QueenSide, CastlingRight::NoRight.merge(CastlingRight::QueenSide));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn remove() {
+        assert_eq!(CastlingRight::QueenSide, CastlingRight::BothSide.remove(CastlingRight::KingSide));
+        assert_eq!(CastlingRight::KingSide, CastlingRight::BothSide.remove(CastlingRight::QueenSide));
+        assert_eq!(CastlingRight::NoRight, CastlingRight::BothSide.remove(CastlingRight::BothSide));
+        assert_eq!(CastlingRight::KingSide, CastlingRight::KingSide.remove(CastlingRight::QueenSide));
+        assert_eq!(CastlingRight::BothSide, CastlingRight::BothSide.remove(CastlingRight::NoRight));
+    }
+
+    #[test]
+    fn has() {
+        assert!(CastlingRight::BothSide.has(CastlingRight::KingSide));
+        assert!(CastlingRight::KingSide.has(CastlingRight::KingSide));
+        assert!(!CastlingRight::KingSide.has(CastlingRight::QueenSide));
+        assert!(!CastlingRight::NoRight.has(CastlingRight::QueenSide));
+    }
+
+    #[test]
+    fn castling_side() {
+        use crate::engine::board::piece::castling::castling_side;
+        use crate::engine::board::piece::color::Color;
+        use crate::engine::board::square::{File, Rank, Square};
+
+        let e1 = Square::from_pos(Rank::A, File::Fifth);
+        let g1 = Square::from_pos(Rank::A, File::Seventh);
+        let c8 = Square::from_pos(Rank::H, File::Third);
+        let e8 = Square::from_pos(Rank::H, File::Fifth);
+        assert_eq!(Some(CastlingRight::KingSide), castling_side(Color::White, e1, g1));
+        assert_eq!(Some(CastlingRight::QueenSide), castling_side(Color::Black, e8, c8));
+        assert_eq!(None, castling_side(Color::Black, e1, g1));
+    }
+}
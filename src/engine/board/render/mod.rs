@@ -0,0 +1,244 @@
+//! This module implements rendering a `Board` as text, for terminals and debugging.
+//!
+//! The renderer shows the pieces as letters or as Unicode figurines, with the rank and file labels
+//! and the side to move:
+//!
+//! ```text
+//! 8 r n b q k b n r
+//! 7 p p p p p p p p
+//! 6 . . . . . . . .
+//! 5 . . . . . . . .
+//! 4 . . . .[P]. . .
+//! 3 . . . . . . . .
+//! 2 P P P P[.]P P P
+//! 1 R N B Q K B N R
+//!   a b c d e f g h
+//! Black to move
+//! ```
+//!
+//! Highlighted squares, like the last move 1. e4 above, are written between brackets, or with a
+//! different background colour if ANSI colours are enabled.
+
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::{File, Square};
+
+#[cfg(test)]
+mod tests;
+
+/// Resets the colours of the terminal.
+const ANSI_RESET: &str = "\x1b[0m";
+/// The background colour of the light squares.
+const ANSI_LIGHT_SQUARE: &str = "\x1b[47m";
+/// The background colour of the dark squares.
+const ANSI_DARK_SQUARE: &str = "\x1b[100m";
+/// The background colour of the highlighted squares.
+const ANSI_HIGHLIGHT: &str = "\x1b[43m";
+/// The foreground colour of the white pieces.
+const ANSI_WHITE_PIECE: &str = "\x1b[1;97m";
+/// The foreground colour of the black pieces.
+const ANSI_BLACK_PIECE: &str = "\x1b[1;30m";
+
+/// The way the pieces are written.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PieceStyle {
+    /// The letters of FEN: upper case for White, lower case for Black.
+    Letters,
+    /// The Unicode chess symbols, like `♔` and `♚`.
+    Figurines,
+}
+
+/// Renders boards with configurable style, orientation and highlights.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut renderer = BoardRenderer::new();
+/// renderer.set_style(PieceStyle::Figurines).set_orientation(Color::Black);
+/// println!("{}", renderer.render(&Board::new()));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BoardRenderer {
+    style: PieceStyle,
+    orientation: Color,
+    labels: bool,
+    side_to_move: bool,
+    highlight: BitBoard,
+    ansi: bool,
+}
+
+impl Default for BoardRenderer {
+    fn default() -> Self {
+        BoardRenderer::new()
+    }
+}
+
+impl BoardRenderer {
+    /// Creates a renderer which writes letters with labels and the side to move, from White's side.
+    pub fn new() -> BoardRenderer {
+        BoardRenderer {
+            style: PieceStyle::Letters,
+            orientation: Color::White,
+            labels: true,
+            side_to_move: true,
+            highlight: BitBoard::empty(),
+            ansi: false,
+        }
+    }
+
+    /// Sets the way the pieces are written.
+    pub fn set_style(&mut self, style: PieceStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the side at the bottom of the board.
+    pub fn set_orientation(&mut self, orientation: Color) -> &mut Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets whether the rank and file labels are written.
+    pub fn set_labels(&mut self, labels: bool) -> &mut Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets whether the side to move is written below the board.
+    pub fn set_side_to_move(&mut self, side_to_move: bool) -> &mut Self {
+        self.side_to_move = side_to_move;
+        self
+    }
+
+    /// Sets the highlighted squares.
+    pub fn set_highlight(&mut self, squares: BitBoard) -> &mut Self {
+        self.highlight = squares;
+        self
+    }
+
+    /// Highlights the source and the destination of the move, in addition to the other highlights.
+    pub fn set_last_move(&mut self, chess_move: ChessMove) -> &mut Self {
+        self.highlight = self.highlight
+            .set(chess_move.get_source())
+            .set(chess_move.get_destination());
+        self
+    }
+
+    /// Sets whether the squares and pieces are coloured with ANSI escape codes.
+    pub fn set_ansi(&mut self, ansi: bool) -> &mut Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Renders the board. Every line, including the last one, ends with a newline.
+    pub fn render(&self, board: &Board) -> String {
+        let flipped = self.orientation == Color::Black;
+        // The board is rotated when Black is at the bottom, this returns the square on the board
+        let to_board = |square: Square| if flipped { Square::new(63 - square.to_index() as u8) } else { square };
+        let occupied = if flipped { board.pieces().rotate_180() } else { board.pieces() };
+
+        let rows = occupied.format_by(|square, is_occupied| {
+            let previous = match square.get_file() {
+                File::First => None,
+                _ => Some(to_board(Square::new(square.to_index() as u8 - 1))),
+            };
+            let mut cell = self.format_square(board, to_board(square), is_occupied, previous);
+            if !self.ansi && square.get_file() == File::Eighth && self.highlight.is_set(to_board(square)) {
+                cell.push(']');
+            }
+            cell
+        });
+
+        let mut result = String::new();
+        for (row, line) in rows.lines().enumerate() {
+            if self.labels {
+                let rank = if flipped { row + 1 } else { 8 - row };
+                result.push_str(&rank.to_string());
+                if self.ansi {
+                    result.push(' ');
+                }
+            }
+            result.push_str(line);
+            result.push('\n');
+        }
+
+        if self.labels {
+            result.push_str(if self.ansi { "  " } else { " " });
+            for index in 1..=8 {
+                let file = File::from_index(if flipped { 9 - index } else { index }).unwrap();
+                if self.ansi {
+                    result.push_str(&format!(" {} ", file));
+                } else {
+                    result.push_str(&format!(" {}", file));
+                }
+            }
+            result.push('\n');
+        }
+
+        if self.side_to_move {
+            let side = match board.get_turn() {
+                Color::White => "White",
+                Color::Black => "Black",
+            };
+            result.push_str(&format!("{} to move\n", side));
+        }
+
+        result
+    }
+
+    /// Formats a square: a separator followed by the piece, or a coloured cell with ANSI colours.
+    ///
+    /// The piece is only looked up on the `occupied` squares.
+    fn format_square(&self, board: &Board, square: Square, occupied: bool, previous: Option<Square>) -> String {
+        let piece = if occupied { piece_at(board, square) } else { None };
+
+        if self.ansi {
+            let background = if self.highlight.is_set(square) {
+                ANSI_HIGHLIGHT
+            } else if square.is_light() {
+                ANSI_LIGHT_SQUARE
+            } else {
+                ANSI_DARK_SQUARE
+            };
+            let content = match piece {
+                Some((piece, color)) => {
+                    let foreground = if color == Color::White { ANSI_WHITE_PIECE } else { ANSI_BLACK_PIECE };
+                    format!("{}{}", foreground, self.format_piece(piece, color))
+                }
+                None => " ".to_owned(),
+            };
+            format!("{} {} {}", background, content, ANSI_RESET)
+        } else {
+            let separator = if self.highlight.is_set(square) {
+                '['
+            } else if previous.is_some_and(|previous| self.highlight.is_set(previous)) {
+                ']'
+            } else {
+                ' '
+            };
+            let content = match piece {
+                Some((piece, color)) => self.format_piece(piece, color),
+                None => ".".to_owned(),
+            };
+            format!("{}{}", separator, content)
+        }
+    }
+
+    fn format_piece(&self, piece: Piece, color: Color) -> String {
+        match (self.style, color) {
+            (PieceStyle::Letters, Color::White) => piece.to_string(),
+            (PieceStyle::Letters, Color::Black) => piece.to_string().to_ascii_lowercase(),
+            (PieceStyle::Figurines, color) => piece.to_figurine(color).to_string(),
+        }
+    }
+}
+
+fn piece_at(board: &Board, square: Square) -> Option<(Piece, Color)> {
+    match board.piece_at(square, Color::White) {
+        Some(piece) => Some((piece, Color::White)),
+        None => board.piece_at(square, Color::Black).map(|piece| (piece, Color::Black)),
+    }
+}
@@ -0,0 +1,74 @@
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::render::{BoardRenderer, PieceStyle};
+use crate::engine::board::square::Square;
+
+#[test]
+fn render_initial_board() {
+    let expected = "8 r n b q k b n r\n\
+                    7 p p p p p p p p\n\
+                    6 . . . . . . . .\n\
+                    5 . . . . . . . .\n\
+                    4 . . . . . . . .\n\
+                    3 . . . . . . . .\n\
+                    2 P P P P P P P P\n\
+                    1 R N B Q K B N R\n  \
+                    a b c d e f g h\n\
+                    White to move\n";
+    assert_eq!(expected, BoardRenderer::new().render(&Board::new()));
+    assert_eq!(expected, Board::new().to_string());
+}
+
+#[test]
+fn render_black_orientation() {
+    let board = Board::new().make_move(Board::new().parse_san("e4").unwrap()).unwrap();
+    let mut renderer = BoardRenderer::new();
+    renderer.set_orientation(Color::Black).set_style(PieceStyle::Figurines);
+
+    let expected = "1 ♖ ♘ ♗ ♔ ♕ ♗ ♘ ♖\n\
+                    2 ♙ ♙ ♙ . ♙ ♙ ♙ ♙\n\
+                    3 . . . . . . . .\n\
+                    4 . . . ♙ . . . .\n\
+                    5 . . . . . . . .\n\
+                    6 . . . . . . . .\n\
+                    7 ♟ ♟ ♟ ♟ ♟ ♟ ♟ ♟\n\
+                    8 ♜ ♞ ♝ ♚ ♛ ♝ ♞ ♜\n  \
+                    h g f e d c b a\n\
+                    Black to move\n";
+    assert_eq!(expected, renderer.render(&board));
+}
+
+#[test]
+fn render_highlights() {
+    let chess_move = Board::new().parse_san("e4").unwrap();
+    let board = Board::new().make_move(chess_move).unwrap();
+    let mut renderer = BoardRenderer::new();
+    renderer.set_labels(false).set_side_to_move(false)
+        .set_highlight(Square::from_string("h8").unwrap().as_bb())
+        .set_last_move(chess_move);
+
+    let expected = " r n b q k b n[r]\n \
+                    p p p p p p p p\n \
+                    . . . . . . . .\n \
+                    . . . . . . . .\n \
+                    . . . .[P]. . .\n \
+                    . . . . . . . .\n \
+                    P P P P[.]P P P\n \
+                    R N B Q K B N R\n";
+    assert_eq!(expected, renderer.render(&board));
+}
+
+#[test]
+fn render_ansi() {
+    let mut renderer = BoardRenderer::new();
+    renderer.set_ansi(true).set_highlight(BitBoard::from(1));
+    let rendered = renderer.render(&Board::new());
+    let lines: Vec<_> = rendered.lines().collect();
+
+    assert_eq!(10, lines.len());
+    // a1 is highlighted, b1 is a light square
+    assert!(lines[7].starts_with("1 \x1b[43m \x1b[1;97mR \x1b[0m\x1b[47m \x1b[1;97mN \x1b[0m"));
+    assert!(lines[0].starts_with("8 \x1b[47m \x1b[1;30mr \x1b[0m"));
+    assert_eq!("   a  b  c  d  e  f  g  h ", lines[8]);
+}
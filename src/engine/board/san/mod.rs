@@ -0,0 +1,241 @@
+//! This module implements the Standard Algebraic Notation (SAN) of moves.
+//!
+//! A move is written as the letter of the moving piece (omitted for pawns), the source file and/or
+//! rank if more than one piece of that type could move to the destination, an `x` for captures,
+//! the destination square, and the promoted piece like `=Q`. Castling is written as `O-O` or
+//! `O-O-O`, and a `+` or `#` suffix marks check or checkmate.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::{castling, Piece};
+use crate::engine::board::piece::castling::CastlingRight;
+use crate::engine::board::square::Square;
+
+#[cfg(test)]
+mod tests;
+
+/// Represents an error which happened while parsing a move in SAN.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SanError {
+    /// The move is not written in SAN.
+    InvalidSan(String),
+    /// There is no legal move in the position which matches the move.
+    IllegalMove(String),
+    /// More than one legal move in the position matches the move.
+    AmbiguousMove(String),
+}
+
+impl Display for SanError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            SanError::InvalidSan(san) => write!(f, "Invalid SAN: {}", san),
+            SanError::IllegalMove(san) => write!(f, "Illegal move: {}", san),
+            SanError::AmbiguousMove(san) => write!(f, "Ambiguous move: {}", san),
+        }
+    }
+}
+
+impl Error for SanError {}
+
+/// The parts of a move written in SAN.
+struct SanMove {
+    piece: Piece,
+    source_file: Option<u8>,
+    source_rank: Option<u8>,
+    destination: Square,
+    promotion: Option<Piece>,
+}
+
+/// Parses a move written in SAN, and returns the matching legal move of the position.
+///
+/// Check and checkmate suffixes, annotations like `!?` and the `e.p.` suffix of en passant
+/// captures are accepted, but not verified. Castling can be written with zeros as well.
+pub fn parse_san(board: &Board, san: &str) -> Result<ChessMove, SanError> {
+    let trimmed = san.trim();
+    let trimmed = trimmed.strip_suffix("e.p.").unwrap_or(trimmed).trim_end();
+    let trimmed = trimmed.trim_end_matches(|c| "+#!?".contains(c));
+
+    let color = board.get_turn();
+    let chess_move = match trimmed {
+        "O-O" | "0-0" => castling_move(board, CastlingRight::KingSide),
+        "O-O-O" | "0-0-0" => castling_move(board, CastlingRight::QueenSide),
+        _ => {
+            let parsed = parse_parts(trimmed).ok_or_else(|| SanError::InvalidSan(san.to_owned()))?;
+            let mut candidates = board.legal_moves().into_iter().filter(|chess_move| {
+                let src = chess_move.get_source().to_index() as u8;
+                chess_move.get_destination() == parsed.destination
+                    && chess_move.get_promotion() == parsed.promotion
+                    && board.piece_at(chess_move.get_source(), color) == Some(parsed.piece)
+                    && parsed.source_file.is_none_or(|file| src % 8 == file)
+                    && parsed.source_rank.is_none_or(|rank| src / 8 == rank)
+            });
+
+            match (candidates.next(), candidates.next()) {
+                (Some(chess_move), None) => Some(chess_move),
+                (Some(_), Some(_)) => return Err(SanError::AmbiguousMove(san.to_owned())),
+                _ => None,
+            }
+        }
+    };
+
+    chess_move.ok_or_else(|| SanError::IllegalMove(san.to_owned()))
+}
+
+/// Returns the castling move to the specified side, if it is legal.
+fn castling_move(board: &Board, side: CastlingRight) -> Option<ChessMove> {
+    let color = board.get_turn();
+    let chess_move = ChessMove::new(castling::king_square(color), castling::king_destination(color, side));
+    board.legal_moves().into_iter().find(|legal| *legal == chess_move)
+}
+
+/// Splits a (non-castling) move to its' parts, or returns `None` if it is not written in SAN.
+fn parse_parts(san: &str) -> Option<SanMove> {
+    let mut chars: Vec<char> = san.chars().collect();
+
+    let piece = match chars.first()? {
+        'a'..='h' => Piece::Pawn,
+        letter => {
+            let piece = piece_from_letter(*letter)?;
+            chars.remove(0);
+            piece
+        }
+    };
+
+    // The promotion is written after the destination, usually with an equal sign
+    let promotion = match chars.last() {
+        Some(letter) if letter.is_ascii_uppercase() => {
+            let promotion = piece_from_letter(*letter).filter(|promoted| *promoted != Piece::King)?;
+            chars.pop();
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            }
+            Some(promotion)
+        }
+        _ => None,
+    };
+    if promotion.is_some() && piece != Piece::Pawn {
+        return None;
+    }
+
+    if chars.len() < 2 {
+        return None;
+    }
+    let destination = Square::from_string(&chars[chars.len() - 2..].iter().collect::<String>())?;
+    chars.truncate(chars.len() - 2);
+
+    let capture = chars.last() == Some(&'x');
+    if capture {
+        chars.pop();
+    }
+
+    let mut source_file = None;
+    let mut source_rank = None;
+    match chars.as_slice() {
+        [] => (),
+        [file @ 'a'..='h'] => source_file = Some(*file as u8 - b'a'),
+        [rank @ '1'..='8'] => source_rank = Some(*rank as u8 - b'1'),
+        [file @ 'a'..='h', rank @ '1'..='8'] => {
+            source_file = Some(*file as u8 - b'a');
+            source_rank = Some(*rank as u8 - b'1');
+        }
+        _ => return None,
+    }
+
+    // Pawn captures are written with the source file, other pawn moves stay on their' file
+    if piece == Piece::Pawn {
+        if capture != source_file.is_some() || source_rank.is_some() {
+            return None;
+        }
+        if !capture {
+            source_file = Some((destination.to_index() % 8) as u8);
+        }
+    }
+
+    Some(SanMove {
+        piece,
+        source_file,
+        source_rank,
+        destination,
+        promotion,
+    })
+}
+
+fn piece_from_letter(letter: char) -> Option<Piece> {
+    match letter {
+        'N' => Some(Piece::Knight),
+        'B' => Some(Piece::Bishop),
+        'R' => Some(Piece::Rook),
+        'Q' => Some(Piece::Queen),
+        'K' => Some(Piece::King),
+        _ => None,
+    }
+}
+
+/// Writes a legal move of the position in SAN.
+pub fn to_san(board: &Board, chess_move: ChessMove) -> String {
+    let color = board.get_turn();
+    let src = chess_move.get_source();
+    let dst = chess_move.get_destination();
+    let piece = board.piece_at(src, color).unwrap();
+
+    let mut san = match castling::castling_side(color, src, dst) {
+        Some(CastlingRight::KingSide) if piece == Piece::King => "O-O".to_owned(),
+        Some(CastlingRight::QueenSide) if piece == Piece::King => "O-O-O".to_owned(),
+        _ => {
+            // A pawn moving diagonally always captures, even if the destination is empty (en passant)
+            let capture = board.piece_at(dst, !color).is_some()
+                || (piece == Piece::Pawn && src.to_index() % 8 != dst.to_index() % 8);
+
+            let mut san = String::new();
+            if piece == Piece::Pawn {
+                if capture {
+                    san.push_str(&src.get_file().to_string());
+                }
+            } else {
+                san.push_str(&piece.to_string());
+                san.push_str(&disambiguation(board, chess_move, piece));
+            }
+            if capture {
+                san.push('x');
+            }
+            san.push_str(&dst.to_string());
+            if let Some(promoted) = chess_move.get_promotion() {
+                san.push('=');
+                san.push_str(&promoted.to_string());
+            }
+            san
+        }
+    };
+
+    if let Some(next) = board.make_move(chess_move) {
+        if next.is_check() {
+            san.push(if next.legal_moves().is_empty() { '#' } else { '+' });
+        }
+    }
+    san
+}
+
+/// Returns the source file and/or rank needed to tell the move apart from the moves of the other
+/// pieces of the same type to the same destination.
+fn disambiguation(board: &Board, chess_move: ChessMove, piece: Piece) -> String {
+    let src = chess_move.get_source();
+    let others: Vec<Square> = board.legal_moves().into_iter()
+        .filter(|other| other.get_destination() == chess_move.get_destination() && other.get_source() != src)
+        .filter(|other| board.piece_at(other.get_source(), board.get_turn()) == Some(piece))
+        .map(|other| other.get_source())
+        .collect();
+
+    if others.is_empty() {
+        String::new()
+    } else if others.iter().all(|other| other.get_file() != src.get_file()) {
+        src.get_file().to_string()
+    } else if others.iter().all(|other| other.get_rank() != src.get_rank()) {
+        src.get_rank().to_string()
+    } else {
+        src.to_string()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::san::SanError;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::fen;
+
+#[test]
+fn pawn_and_piece_moves() {
+    let board = Board::new();
+    let e4 = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth));
+    let nf3 = ChessMove::new(Square::from_pos(Rank::A, File::Seventh), Square::from_pos(Rank::C, File::Sixth));
+
+    assert_eq!(Ok(e4), board.parse_san("e4"));
+    assert_eq!(Ok(nf3), board.parse_san("Nf3"));
+    assert_eq!(Ok(nf3), board.parse_san("Ng1f3!?"));
+    assert_eq!("e4", board.to_san(e4));
+    assert_eq!("Nf3", board.to_san(nf3));
+}
+
+#[test]
+fn invalid_san() {
+    let board = Board::new();
+    assert_eq!(Err(SanError::InvalidSan("Xe4".to_owned())), board.parse_san("Xe4"));
+    assert_eq!(Err(SanError::InvalidSan("".to_owned())), board.parse_san(""));
+    assert_eq!(Err(SanError::InvalidSan("Nf3=Q".to_owned())), board.parse_san("Nf3=Q"));
+    assert_eq!(Err(SanError::InvalidSan("e2e4e".to_owned())), board.parse_san("e2e4e"));
+    assert_eq!(Err(SanError::IllegalMove("e5".to_owned())), board.parse_san("e5"));
+    assert_eq!(Err(SanError::IllegalMove("O-O".to_owned())), board.parse_san("O-O"));
+}
+
+#[test]
+fn disambiguation() {
+    // Knights on b8 and f6 can both go to d7, rooks on a2 and a5 can both go to a3
+    let board = fen::from_fen("1n2k3/8/5n2/r7/8/8/r7/4K3 b - - 0 1").unwrap();
+    let nbd7 = ChessMove::new(Square::from_pos(Rank::H, File::Second), Square::from_pos(Rank::G, File::Fourth));
+    let r5a3 = ChessMove::new(Square::from_pos(Rank::E, File::First), Square::from_pos(Rank::C, File::First));
+
+    assert_eq!(Ok(nbd7), board.parse_san("Nbd7"));
+    assert_eq!(Ok(nbd7), board.parse_san("Nb8d7"));
+    assert_eq!(Ok(r5a3), board.parse_san("R5a3"));
+    assert_eq!(Err(SanError::AmbiguousMove("Nd7".to_owned())), board.parse_san("Nd7"));
+    assert_eq!(Err(SanError::AmbiguousMove("Ra3".to_owned())), board.parse_san("Ra3"));
+    assert_eq!("Nbd7", board.to_san(nbd7));
+    assert_eq!("R5a3", board.to_san(r5a3));
+}
+
+#[test]
+fn disambiguation_file_and_rank() {
+    // Queens on a1, a3 and c1 can all go to b2
+    let board = fen::from_fen("4k3/8/8/8/8/Q7/8/Q1Q1K3 w - - 0 1").unwrap();
+    let qa1b2 = ChessMove::new(Square::from_pos(Rank::A, File::First), Square::from_pos(Rank::B, File::Second));
+    assert_eq!("Qa1b2", board.to_san(qa1b2));
+    assert_eq!(Ok(qa1b2), board.parse_san("Qa1b2"));
+}
+
+#[test]
+fn captures_and_en_passant() {
+    let board = fen::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let exf6 = ChessMove::new(Square::from_pos(Rank::E, File::Fifth), Square::from_pos(Rank::F, File::Sixth));
+
+    assert_eq!(Ok(exf6), board.parse_san("exf6"));
+    assert_eq!(Ok(exf6), board.parse_san("exf6 e.p."));
+    assert_eq!(Ok(exf6), board.parse_san("exf6e.p."));
+    assert_eq!(Err(SanError::IllegalMove("f6".to_owned())), board.parse_san("f6"));
+    assert_eq!("exf6", board.to_san(exf6));
+
+    let bxd5 = fen::from_fen("4k3/8/8/3p4/8/5B2/8/4K3 w - - 0 1").unwrap();
+    let capture = ChessMove::new(Square::from_pos(Rank::C, File::Sixth), Square::from_pos(Rank::E, File::Fourth));
+    assert_eq!("Bxd5", bxd5.to_san(capture));
+    assert_eq!(Ok(capture), bxd5.parse_san("Bxd5"));
+}
+
+#[test]
+fn promotion() {
+    let board = fen::from_fen("1n2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let a8q = ChessMove::new_promote(Square::from_pos(Rank::G, File::First), Square::from_pos(Rank::H, File::First), Piece::Queen);
+    let axb8n = ChessMove::new_promote(Square::from_pos(Rank::G, File::First), Square::from_pos(Rank::H, File::Second), Piece::Knight);
+
+    assert_eq!(Ok(a8q), board.parse_san("a8=Q+"));
+    assert_eq!(Ok(a8q), board.parse_san("a8Q"));
+    assert_eq!(Ok(axb8n), board.parse_san("axb8=N"));
+    assert_eq!(Err(SanError::IllegalMove("a8".to_owned())), board.parse_san("a8"));
+    assert_eq!("a8=Q", board.to_san(a8q));
+    assert_eq!("axb8=N", board.to_san(axb8n));
+}
+
+#[test]
+fn castling() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+    let short = ChessMove::new(Square::from_pos(Rank::H, File::Fifth), Square::from_pos(Rank::H, File::Seventh));
+    let long = ChessMove::new(Square::from_pos(Rank::H, File::Fifth), Square::from_pos(Rank::H, File::Third));
+
+    assert_eq!(Ok(short), board.parse_san("O-O"));
+    assert_eq!(Ok(short), board.parse_san("0-0"));
+    assert_eq!(Ok(long), board.parse_san("O-O-O+"));
+    assert_eq!("O-O", board.to_san(short));
+    assert_eq!("O-O-O", board.to_san(long));
+}
+
+#[test]
+fn check_and_checkmate() {
+    // Fool's mate
+    let board = fen::from_fen("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2").unwrap();
+    let qh4 = ChessMove::new(Square::from_pos(Rank::H, File::Fourth), Square::from_pos(Rank::D, File::Eighth));
+    assert_eq!("Qh4#", board.to_san(qh4));
+    assert_eq!(Ok(qh4), board.parse_san("Qh4#"));
+
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    let ra8 = ChessMove::new(Square::from_pos(Rank::A, File::First), Square::from_pos(Rank::H, File::First));
+    assert_eq!("Ra8+", board.to_san(ra8));
+}
+
+#[test]
+fn round_trip_every_legal_move() {
+    let board = fen::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    for chess_move in board.legal_moves() {
+        assert_eq!(Ok(chess_move), board.parse_san(&board.to_san(chess_move)));
+    }
+}
@@ -0,0 +1,102 @@
+//! This module implements the static exchange evaluation (SEE) of a move.
+//!
+//! The exchange evaluation plays every capture on the destination of the move, always with the
+//! least valuable attacker, and either player may stop capturing when it would lose material. When
+//! a piece is removed from the square, the sliding pieces behind it join the exchange (x-rays).
+//!
+//! The pins and the checks are ignored, so the result is only an estimate, but it is cheap, and it
+//! does not need to make any move.
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::{general, Piece, sliding};
+use crate::engine::board::square::Square;
+
+#[cfg(test)]
+mod tests;
+
+/// The pieces in the order they join the exchange, from the least valuable one.
+const EXCHANGE_ORDER: [Piece; 6] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+    Piece::King
+];
+
+/// Returns the material won by the move of the player who have to move, in centipawns, if both
+/// players continue the exchange on the destination square as long as it is worth it.
+pub fn see(board: &Board, chess_move: ChessMove) -> i32 {
+    let src = chess_move.get_source();
+    let dst = chess_move.get_destination();
+    let color = board.get_turn();
+    let moving = match board.piece_at(src, color) {
+        Some(piece) => piece,
+        None => return 0,
+    };
+
+    let mut occupied = board.pieces() ^ src.as_bb();
+    let mut gains = Vec::with_capacity(32);
+    gains.push(board.get_captured_piece(chess_move).map_or(0, |piece| piece.get_value()));
+    if moving == Piece::Pawn && board.piece_at(dst, !color).is_none() && src.get_file() != dst.get_file() {
+        // The pawn captured en passant is not on the destination square
+        occupied ^= Square::new((src.to_index() & !7 | dst.to_index() & 7) as u8).as_bb();
+    }
+
+    // The value of the piece standing on the destination, which can be captured next
+    let mut target = match chess_move.get_promotion() {
+        Some(promotion) => {
+            gains[0] += promotion.get_value() - Piece::Pawn.get_value();
+            promotion.get_value()
+        }
+        None => moving.get_value(),
+    };
+
+    let diagonal = board.pieces_by_type(Piece::Bishop) | board.pieces_by_type(Piece::Queen);
+    let orthogonal = board.pieces_by_type(Piece::Rook) | board.pieces_by_type(Piece::Queen);
+    let mut attackers = general::square_attacked_by_occupied(dst, board, occupied);
+    let mut side = !color;
+
+    loop {
+        let own = attackers & board.pieces_by_color(side);
+        let attacker = EXCHANGE_ORDER.iter()
+            .map(|piece| (*piece, own & board.pieces_by_type(*piece)))
+            .find(|(_, pieces)| pieces.is_not_empty());
+        let (piece, mut pieces) = match attacker {
+            Some(attacker) => attacker,
+            None => break,
+        };
+        // The king cannot capture a defended piece
+        if piece == Piece::King && (attackers & board.pieces_by_color(!side)).is_not_empty() {
+            break;
+        }
+
+        gains.push(target - gains[gains.len() - 1]);
+        target = piece.get_value();
+
+        // The sliding pieces behind the attacker join the exchange
+        occupied ^= pieces.next().unwrap().as_bb();
+        attackers &= occupied;
+        if piece == Piece::Pawn || piece == Piece::Bishop || piece == Piece::Queen {
+            attackers |= sliding::get_piece_attacks(Piece::Bishop, dst, occupied) & diagonal & occupied;
+        }
+        if piece == Piece::Rook || piece == Piece::Queen {
+            attackers |= sliding::get_piece_attacks(Piece::Rook, dst, occupied) & orthogonal & occupied;
+        }
+        side = !side;
+    }
+
+    // Both players stop the exchange, when continuing it would lose material
+    while gains.len() > 1 {
+        let last = gains.pop().unwrap();
+        let previous = gains.len() - 1;
+        gains[previous] = -(-gains[previous]).max(last);
+    }
+    gains[0]
+}
+
+/// Checks whether the static exchange evaluation of the move is at least the `threshold`.
+pub fn see_ge(board: &Board, chess_move: ChessMove, threshold: i32) -> bool {
+    see(board, chess_move) >= threshold
+}
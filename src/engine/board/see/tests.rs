@@ -0,0 +1,51 @@
+use crate::engine::board::Board;
+use crate::engine::fen;
+
+fn see(fen: &str, uci: &str) -> i32 {
+    let board = fen::from_fen(fen).unwrap();
+    board.see(board.parse_uci(uci).unwrap())
+}
+
+#[test]
+fn undefended_piece() {
+    assert_eq!(100, see("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5"));
+}
+
+#[test]
+fn defended_piece() {
+    // Nxe5 Nxe5 loses the knight for a pawn
+    assert_eq!(-220, see("4k3/3n4/8/4p3/8/3N4/8/4K3 w - - 0 1", "d3e5"));
+    // With the rook, Black does not recapture
+    assert_eq!(100, see("4k3/3n4/8/4p3/8/3N4/4R3/4K3 w - - 0 1", "d3e5"));
+    assert_eq!(-220, see("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5"));
+}
+
+#[test]
+fn x_rays() {
+    // The rook behind the first one defends it, so the king cannot recapture
+    assert_eq!(500, see("4k3/4r3/8/8/8/8/4R3/4R1K1 w - - 0 1", "e2e7"));
+    assert_eq!(0, see("4k3/4r3/8/8/8/8/4R3/6K1 w - - 0 1", "e2e7"));
+    // The queen behind the bishop recaptures the pawn
+    assert_eq!(-130, see("4k3/8/5p2/4p3/8/8/1B6/Q3K3 w - - 0 1", "b2e5"));
+    assert_eq!(-230, see("4k3/8/5p2/4p3/8/8/1B6/4K3 w - - 0 1", "b2e5"));
+}
+
+#[test]
+fn quiet_and_special_moves() {
+    assert_eq!(-320, see("4k3/8/8/8/3p4/8/8/1N2K3 w - - 0 1", "b1c3"));
+    assert_eq!(0, see(fen::INITIAL_BOARD, "g1f3"));
+    // The pawn captured en passant is not on the destination square
+    assert_eq!(100, see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"));
+    // The promoted queen is captured
+    assert_eq!(-100, see("3rk3/2P5/8/8/8/8/8/4K3 w - - 0 1", "c7c8q"));
+    assert_eq!(400, see("3rk3/2P5/8/8/8/8/8/4K3 w - - 0 1", "c7d8q"));
+}
+
+#[test]
+fn see_ge() {
+    let board = fen::from_fen("4k3/3n4/8/4p3/8/3N4/4R3/4K3 w - - 0 1").unwrap();
+    let capture = board.parse_uci("d3e5").unwrap();
+    assert!(board.see_ge(capture, 100));
+    assert!(!board.see_ge(capture, 101));
+    assert!(Board::new().see_ge(Board::new().parse_uci("e2e4").unwrap(), 0));
+}
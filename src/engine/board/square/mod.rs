@@ -42,14 +42,14 @@ pub struct Square(u8);
 impl Display for Rank {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         let rank = match *self {
-            Rank::A => "a",
-            Rank::B => "b",
-            Rank::C => "c",
-            Rank::D => "d",
-            Rank::E => "e",
-            Rank::F => "f",
-            Rank::G => "g",
-            Rank::H => "h",
+            Rank::A => "1",
+            Rank::B => "2",
+            Rank::C => "3",
+            Rank::D => "4",
+            Rank::E => "5",
+            Rank::F => "6",
+            Rank::G => "7",
+            Rank::H => "8",
         };
 
         write!(f, "{}", rank)
@@ -57,17 +57,17 @@ impl Display for Rank {
 }
 
 impl Rank {
-    /// Converts a string to a Rank. Only "a".."h" values are allowed.
+    /// Converts a string to a Rank. Only "1".."8" values are allowed.
     pub fn from_string(id: &str) -> Option<Rank> {
-        match id.to_lowercase().trim().as_ref() {
-            "a" => Some(Rank::A),
-            "b" => Some(Rank::B),
-            "c" => Some(Rank::C),
-            "d" => Some(Rank::D),
-            "e" => Some(Rank::E),
-            "f" => Some(Rank::F),
-            "g" => Some(Rank::G),
-            "h" => Some(Rank::H),
+        match id.trim() {
+            "1" => Some(Rank::A),
+            "2" => Some(Rank::B),
+            "3" => Some(Rank::C),
+            "4" => Some(Rank::D),
+            "5" => Some(Rank::E),
+            "6" => Some(Rank::F),
+            "7" => Some(Rank::G),
+            "8" => Some(Rank::H),
             _ => None
         }
     }
@@ -94,6 +94,21 @@ impl Rank {
 }
 
 impl File {
+    /// Converts a string to a File. Only "a".."h" values are allowed.
+    pub fn from_string(id: &str) -> Option<File> {
+        match id.to_lowercase().trim() {
+            "a" => Some(File::First),
+            "b" => Some(File::Second),
+            "c" => Some(File::Third),
+            "d" => Some(File::Fourth),
+            "e" => Some(File::Fifth),
+            "f" => Some(File::Sixth),
+            "g" => Some(File::Seventh),
+            "h" => Some(File::Eighth),
+            _ => None
+        }
+    }
+
     /// Converts an integer to a File, only values 1..8 are allowed.
     pub fn from_index(index: u8) -> Option<File> {
         match index {
@@ -118,14 +133,14 @@ impl File {
 impl Display for File {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         let file = match *self {
-            File::First => "1",
-            File::Second => "2",
-            File::Third => "3",
-            File::Fourth => "4",
-            File::Fifth => "5",
-            File::Sixth => "6",
-            File::Seventh => "7",
-            File::Eighth => "8",
+            File::First => "a",
+            File::Second => "b",
+            File::Third => "c",
+            File::Fourth => "d",
+            File::Fifth => "e",
+            File::Sixth => "f",
+            File::Seventh => "g",
+            File::Eighth => "h",
         };
 
         write!(f, "{}", file)
@@ -142,7 +157,7 @@ impl Square {
     /// Creates a new index using the specified raw value. Accepts values between 0..63.
     pub fn new(index: u8) -> Square {
         if index > 63 {
-            panic!(format!("Invalid square value: {}!", index))
+            panic!("Invalid square value: {}!", index)
         }
 
         Square(index)
@@ -155,10 +170,22 @@ impl Square {
         Square::new(rank * 8 + file)
     }
 
+    /// Converts a string in algebraic notation, like "e4", to a square.
+    pub fn from_string(id: &str) -> Option<Square> {
+        let id = id.trim();
+        if id.len() != 2 || !id.is_char_boundary(1) {
+            return None;
+        }
+
+        let file = File::from_string(&id[..1])?;
+        let rank = Rank::from_string(&id[1..])?;
+        Some(Square::from_pos(rank, file))
+    }
+
     /// Converts a Bitboard to a square.
     pub fn from_bb(bb: BitBoard) -> Square {
         // This function makes no sense if it is called for a bitboard with multiple bits toggled
-        debug_assert!(bb.0 % 2 == 0 || bb.0 == 1);
+        debug_assert!(bb.0.is_multiple_of(2) || bb.0 == 1);
 
         let value_index = bb.bit_scan_fw();
         let rank: u8 = (value_index / 8) as u8 + 1;
@@ -186,10 +213,16 @@ impl Square {
     pub fn get_file(&self) -> File {
         File::from_index(self.0 % 8 + 1).unwrap()
     }
+
+    /// Returns whether the square is a light square. The corner square `a1` is dark.
+    pub fn is_light(&self) -> bool {
+        (self.0 / 8 + self.0 % 8) % 2 == 1
+    }
 }
 
 impl Display for Square {
+    /// Writes the square in algebraic notation: the file letter followed by the rank digit, like "e4".
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "{}{}", self.get_rank(), self.get_file())
+        write!(f, "{}{}", self.get_file(), self.get_rank())
     }
 }
@@ -4,21 +4,27 @@ mod rank_test {
 
     #[test]
     fn from_id() {
-        assert_eq!(Some(Rank::A), Rank::from_string("a"));
-        assert_eq!(Some(Rank::B), Rank::from_string("b"));
-        assert_eq!(Some(Rank::C), Rank::from_string("c"));
-        assert_eq!(Some(Rank::D), Rank::from_string("d"));
-        assert_eq!(Some(Rank::E), Rank::from_string("e"));
-        assert_eq!(Some(Rank::F), Rank::from_string("f"));
-        assert_eq!(Some(Rank::G), Rank::from_string("g"));
-        assert_eq!(Some(Rank::H), Rank::from_string("h"));
+        assert_eq!(Some(Rank::A), Rank::from_string("1"));
+        assert_eq!(Some(Rank::B), Rank::from_string("2"));
+        assert_eq!(Some(Rank::C), Rank::from_string("3"));
+        assert_eq!(Some(Rank::D), Rank::from_string("4"));
+        assert_eq!(Some(Rank::E), Rank::from_string("5"));
+        assert_eq!(Some(Rank::F), Rank::from_string("6"));
+        assert_eq!(Some(Rank::G), Rank::from_string("7"));
+        assert_eq!(Some(Rank::H), Rank::from_string("8"));
     }
 
     #[test]
     fn from_id_err() {
         assert_eq!(None, Rank::from_string(""));
-        assert_eq!(None, Rank::from_string("i"));
-        assert_eq!(None, Rank::from_string("1"));
+        assert_eq!(None, Rank::from_string("9"));
+        assert_eq!(None, Rank::from_string("a"));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("1", Rank::A.to_string());
+        assert_eq!("8", Rank::H.to_string());
     }
 
     #[test]
@@ -56,6 +62,21 @@ mod rank_test {
 mod file_test {
     use crate::engine::board::square::File;
 
+    #[test]
+    fn from_id() {
+        assert_eq!(Some(File::First), File::from_string("a"));
+        assert_eq!(Some(File::Fifth), File::from_string("e"));
+        assert_eq!(Some(File::Eighth), File::from_string("H"));
+        assert_eq!(None, File::from_string("i"));
+        assert_eq!(None, File::from_string("1"));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("a", File::First.to_string());
+        assert_eq!("h", File::Eighth.to_string());
+    }
+
     #[test]
     fn from_index() {
         assert_eq!(Some(File::First), File::from_index(1));
@@ -162,4 +183,29 @@ mod square_test {
         let square = Square::from_pos(Rank::H, File::Eighth);
         assert_eq!(63, square.to_index());
     }
+
+    #[test]
+    fn display() {
+        assert_eq!("a1", Square::from_pos(Rank::A, File::First).to_string());
+        assert_eq!("e4", Square::from_pos(Rank::D, File::Fifth).to_string());
+        assert_eq!("h8", Square::from_pos(Rank::H, File::Eighth).to_string());
+    }
+
+    #[test]
+    fn from_string() {
+        assert_eq!(Some(Square::from_pos(Rank::D, File::Fifth)), Square::from_string("e4"));
+        assert_eq!(Some(Square::from_pos(Rank::H, File::First)), Square::from_string("a8"));
+        assert_eq!(None, Square::from_string("e9"));
+        assert_eq!(None, Square::from_string("4e"));
+        assert_eq!(None, Square::from_string("e44"));
+        assert_eq!(None, Square::from_string("é4"));
+    }
+
+    #[test]
+    fn is_light() {
+        assert!(!Square::from_pos(Rank::A, File::First).is_light());
+        assert!(Square::from_pos(Rank::A, File::Second).is_light());
+        assert!(Square::from_pos(Rank::H, File::First).is_light());
+        assert!(!Square::from_pos(Rank::H, File::Eighth).is_light());
+    }
 }
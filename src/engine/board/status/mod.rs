@@ -0,0 +1,96 @@
+//! This module implements the detection of the game outcome: checkmate, stalemate and the
+//! draw rules that can be decided from a single position.
+
+use crate::engine::board::Board;
+use crate::engine::board::constants::{DARK_SQUARES, LIGHT_SQUARES};
+use crate::engine::board::piece::Piece;
+use crate::engine::board::piece::color::Color;
+
+#[cfg(test)]
+mod tests;
+
+/// The number of half moves without capture or pawn move after which a draw can be claimed.
+pub const FIFTY_MOVE_RULE: u16 = 100;
+
+/// The number of half moves without capture or pawn move after which the game is drawn.
+pub const SEVENTY_FIVE_MOVE_RULE: u16 = 150;
+
+/// Represents the status of the game in a position.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameStatus {
+    /// The game goes on.
+    Ongoing,
+    /// The player who have to move is checkmated, the color is the winner.
+    Checkmate(Color),
+    /// The player who have to move has no legal moves, but is not in check.
+    Stalemate,
+    /// There were 50 moves by both players without capture or pawn move, a draw can be claimed.
+    FiftyMoveRule,
+    /// There were 75 moves by both players without capture or pawn move, the game is drawn.
+    SeventyFiveMoveRule,
+    /// Neither player has enough material to checkmate.
+    InsufficientMaterial,
+    /// The same position occurred three times, a draw can be claimed.
+    ///
+    /// Only reported by a `Game`, as it depends on the history of the game.
+    ThreefoldRepetition,
+    /// The same position occurred five times, the game is drawn.
+    ///
+    /// Only reported by a `Game`, as it depends on the history of the game.
+    FivefoldRepetition,
+}
+
+impl GameStatus {
+    /// Checks whether the game is over.
+    ///
+    /// The fifty-move rule and threefold repetition only allow to claim a draw, so the game is not
+    /// over automatically.
+    pub fn is_game_over(&self) -> bool {
+        !matches!(*self, GameStatus::Ongoing | GameStatus::FiftyMoveRule | GameStatus::ThreefoldRepetition)
+    }
+
+    /// Checks whether the game is drawn, or a draw can be claimed.
+    pub fn is_draw(&self) -> bool {
+        !matches!(*self, GameStatus::Ongoing | GameStatus::Checkmate(_))
+    }
+}
+
+/// Determines the status of the game.
+///
+/// Checkmate takes precedence over the move rules, as the last move ends the game.
+pub fn status(board: &Board) -> GameStatus {
+    if board.legal_moves().is_empty() {
+        if board.in_check(board.turn) {
+            GameStatus::Checkmate(!board.turn)
+        } else {
+            GameStatus::Stalemate
+        }
+    } else if board.half_moves >= SEVENTY_FIVE_MOVE_RULE {
+        GameStatus::SeventyFiveMoveRule
+    } else if is_insufficient_material(board) {
+        GameStatus::InsufficientMaterial
+    } else if board.half_moves >= FIFTY_MOVE_RULE {
+        GameStatus::FiftyMoveRule
+    } else {
+        GameStatus::Ongoing
+    }
+}
+
+/// Checks whether neither player can checkmate.
+///
+/// This is the case if there are only kings on the board, or besides the kings there is a single
+/// knight or bishop, or there are only bishops on squares of the same color.
+pub fn is_insufficient_material(board: &Board) -> bool {
+    let kings = board.pieces_by_type(Piece::King);
+    let knights = board.pieces_by_type(Piece::Knight);
+    let bishops = board.pieces_by_type(Piece::Bishop);
+    let others = board.pieces() ^ kings ^ knights ^ bishops;
+
+    if others.is_not_empty() {
+        false
+    } else if knights.is_empty() {
+        (bishops & LIGHT_SQUARES).is_empty() || (bishops & DARK_SQUARES).is_empty()
+    } else {
+        knights.pop_count() == 1 && bishops.is_empty()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::engine::board::Board;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::status::GameStatus;
+use crate::engine::fen;
+
+fn status(input: &str) -> GameStatus {
+    fen::from_fen(input).unwrap().status()
+}
+
+#[test]
+fn status_initial() {
+    assert_eq!(GameStatus::Ongoing, Board::new().status());
+}
+
+#[test]
+fn status_checkmate() {
+    assert_eq!(GameStatus::Checkmate(Color::Black),
+               status("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"));
+    assert_eq!(GameStatus::Checkmate(Color::White),
+               status("r1bqkb1r/pppp1Qpp/2n2n2/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 4"));
+}
+
+#[test]
+fn status_stalemate() {
+    assert_eq!(GameStatus::Stalemate, status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"));
+}
+
+#[test]
+fn status_fifty_move_rule() {
+    assert_eq!(GameStatus::Ongoing, status("4k3/8/8/8/8/8/8/R3K3 w - - 99 80"));
+    assert_eq!(GameStatus::FiftyMoveRule, status("4k3/8/8/8/8/8/8/R3K3 w - - 100 80"));
+}
+
+#[test]
+fn status_seventy_five_move_rule() {
+    assert_eq!(GameStatus::SeventyFiveMoveRule, status("4k3/8/8/8/8/8/8/R3K3 w - - 150 100"));
+}
+
+#[test]
+fn status_checkmate_before_move_rule() {
+    assert_eq!(GameStatus::Checkmate(Color::White), status("R3k3/8/4K3/8/8/8/8/8 b - - 150 100"));
+}
+
+#[test]
+fn status_insufficient_material() {
+    assert_eq!(GameStatus::InsufficientMaterial, status("4k3/8/8/8/8/8/8/4K3 w - - 0 1"));
+    assert_eq!(GameStatus::InsufficientMaterial, status("4k3/8/8/8/8/8/8/2N1K3 w - - 0 1"));
+    assert_eq!(GameStatus::InsufficientMaterial, status("4kb2/8/8/8/8/8/8/4K3 w - - 0 1"));
+    // Bishops on the same color
+    assert_eq!(GameStatus::InsufficientMaterial, status("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+}
+
+#[test]
+fn status_sufficient_material() {
+    // Bishops on different colors
+    assert_eq!(GameStatus::Ongoing, status("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+    assert_eq!(GameStatus::Ongoing, status("4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1"));
+    assert_eq!(GameStatus::Ongoing, status("4kn2/8/8/8/8/8/8/2B1K3 w - - 0 1"));
+    assert_eq!(GameStatus::Ongoing, status("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+}
+
+#[test]
+fn is_game_over() {
+    assert!(!GameStatus::Ongoing.is_game_over());
+    assert!(!GameStatus::FiftyMoveRule.is_game_over());
+    assert!(GameStatus::Stalemate.is_game_over());
+    assert!(GameStatus::Checkmate(Color::White).is_game_over());
+    assert!(GameStatus::FiftyMoveRule.is_draw());
+    assert!(!GameStatus::Checkmate(Color::White).is_draw());
+}
@@ -0,0 +1,215 @@
+//! This module implements rendering a `Board` as a standalone SVG diagram.
+//!
+//! The diagram needs no external assets: the squares are rectangles, and the pieces are the Unicode
+//! chess symbols, drawn as text. The output only depends on the board and the options, so it can be
+//! compared in snapshot tests.
+
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::{File, Rank, Square};
+
+#[cfg(test)]
+mod tests;
+
+/// The size of a square in pixels.
+pub const SQUARE_SIZE: u32 = 45;
+/// The width of the border with the coordinates, in pixels.
+pub const MARGIN: u32 = 20;
+
+const LIGHT_SQUARE: &str = "#f0d9b5";
+const DARK_SQUARE: &str = "#b58863";
+const HIGHLIGHT: &str = "#cdd26a";
+const BORDER: &str = "#404040";
+const COORDINATE: &str = "#e0e0e0";
+const ARROW: &str = "#15781b";
+/// The width of the arrow lines, the arrowheads are scaled with it.
+const ARROW_WIDTH: f64 = 9.0;
+/// The length of the arrowheads, in arrow widths.
+const ARROWHEAD_LENGTH: f64 = 2.5;
+
+/// Renders boards as SVG diagrams, with highlighted squares and arrows.
+///
+/// # Example
+///
+/// ```ignore
+/// let board = Board::new();
+/// let chess_move = board.parse_san("e4").unwrap();
+/// let mut renderer = SvgRenderer::new();
+/// renderer.add_arrow(chess_move).set_highlight(chess_move.get_destination().as_bb());
+/// fs::write("board.svg", renderer.render(&board))?;
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SvgRenderer {
+    orientation: Color,
+    coordinates: bool,
+    highlight: BitBoard,
+    arrows: Vec<ChessMove>,
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        SvgRenderer::new()
+    }
+}
+
+impl SvgRenderer {
+    /// Creates a renderer which draws the board from White's side, with coordinates.
+    pub fn new() -> SvgRenderer {
+        SvgRenderer {
+            orientation: Color::White,
+            coordinates: true,
+            highlight: BitBoard::empty(),
+            arrows: Vec::new(),
+        }
+    }
+
+    /// Sets the side at the bottom of the board.
+    pub fn set_orientation(&mut self, orientation: Color) -> &mut Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Sets whether the coordinates are drawn around the board.
+    pub fn set_coordinates(&mut self, coordinates: bool) -> &mut Self {
+        self.coordinates = coordinates;
+        self
+    }
+
+    /// Sets the highlighted squares.
+    pub fn set_highlight(&mut self, squares: BitBoard) -> &mut Self {
+        self.highlight = squares;
+        self
+    }
+
+    /// Adds an arrow from the source to the destination of the move. Moves to their' own source
+    /// square, like the null move, have no direction, so they are not drawn.
+    pub fn add_arrow(&mut self, chess_move: ChessMove) -> &mut Self {
+        if chess_move.get_source() != chess_move.get_destination() {
+            self.arrows.push(chess_move);
+        }
+        self
+    }
+
+    /// Renders the board as an SVG document.
+    pub fn render(&self, board: &Board) -> String {
+        let margin = self.get_margin();
+        let size = 8 * SQUARE_SIZE + 2 * margin;
+        let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" \
+                               width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n", size);
+
+        svg.push_str(&format!("<defs><marker id=\"arrowhead\" markerWidth=\"{0}\" markerHeight=\"{0}\" \
+                               refX=\"0\" refY=\"{1}\" orient=\"auto\">\
+                               <path d=\"M0,0 L{0},{1} L0,{0} z\" fill=\"{2}\"/></marker></defs>\n",
+                              ARROWHEAD_LENGTH, ARROWHEAD_LENGTH / 2.0, ARROW));
+        if self.coordinates {
+            svg.push_str(&format!("<rect x=\"0\" y=\"0\" width=\"{0}\" height=\"{0}\" fill=\"{1}\"/>\n", size, BORDER));
+            svg.push_str(&self.render_coordinates());
+        }
+
+        for index in 0..64 {
+            let square = Square::new(index);
+            let (x, y) = self.get_position(square);
+            let fill = if self.highlight.is_set(square) {
+                HIGHLIGHT
+            } else if square.is_light() {
+                LIGHT_SQUARE
+            } else {
+                DARK_SQUARE
+            };
+            svg.push_str(&format!("<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"{3}\"/>\n",
+                                  x, y, SQUARE_SIZE, fill));
+        }
+
+        for square in board.pieces() {
+            let color = if board.pieces_by_color(Color::White).is_set(square) { Color::White } else { Color::Black };
+            let piece = board.piece_at(square, color).unwrap();
+            svg.push_str(&self.render_piece(square, piece, color));
+        }
+
+        for &chess_move in &self.arrows {
+            svg.push_str(&self.render_arrow(chess_move));
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn get_margin(&self) -> u32 {
+        if self.coordinates { MARGIN } else { 0 }
+    }
+
+    /// Returns the top left corner of the square in the diagram.
+    fn get_position(&self, square: Square) -> (u32, u32) {
+        let file = square.get_file().to_index() as u32 - 1;
+        let rank = square.get_rank().to_index() as u32 - 1;
+        let (column, row) = match self.orientation {
+            Color::White => (file, 7 - rank),
+            Color::Black => (7 - file, rank),
+        };
+        (self.get_margin() + column * SQUARE_SIZE, self.get_margin() + row * SQUARE_SIZE)
+    }
+
+    /// Returns the center of the square in the diagram.
+    fn get_center(&self, square: Square) -> (f64, f64) {
+        let (x, y) = self.get_position(square);
+        (f64::from(x) + f64::from(SQUARE_SIZE) / 2.0, f64::from(y) + f64::from(SQUARE_SIZE) / 2.0)
+    }
+
+    fn render_coordinates(&self) -> String {
+        let mut result = String::new();
+        let margin = self.get_margin();
+        let far_side = margin + 8 * SQUARE_SIZE + margin / 2;
+
+        for index in 1..=8 {
+            let file = File::from_index(index).unwrap();
+            let (x, _) = self.get_position(Square::from_pos(Rank::A, file));
+            for y in [margin / 2, far_side] {
+                result.push_str(&coordinate(x + SQUARE_SIZE / 2, y, &file.to_string()));
+            }
+
+            let rank = Rank::from_index(index).unwrap();
+            let (_, y) = self.get_position(Square::from_pos(rank, File::First));
+            for x in [margin / 2, far_side] {
+                result.push_str(&coordinate(x, y + SQUARE_SIZE / 2, &rank.to_string()));
+            }
+        }
+
+        result
+    }
+
+    fn render_piece(&self, square: Square, piece: Piece, color: Color) -> String {
+        let (x, y) = self.get_center(square);
+        // The solid symbols are used for both colors, the white pieces are filled with white
+        let (fill, stroke) = match color {
+            Color::White => ("#ffffff", "#000000"),
+            Color::Black => ("#000000", "#000000"),
+        };
+        format!("<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" \
+                 fill=\"{}\" stroke=\"{}\" stroke-width=\"1.5\">{}</text>\n",
+                x, y, SQUARE_SIZE * 7 / 8, fill, stroke, piece.to_figurine(Color::Black))
+    }
+
+    fn render_arrow(&self, chess_move: ChessMove) -> String {
+        let (x1, y1) = self.get_center(chess_move.get_source());
+        let (x2, y2) = self.get_center(chess_move.get_destination());
+
+        // The line is shortened, so the tip of the arrowhead is at the center of the destination
+        let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+        let head = ARROWHEAD_LENGTH * ARROW_WIDTH;
+        let x2 = x2 - (x2 - x1) / length * head;
+        let y2 = y2 - (y2 - y1) / length * head;
+
+        format!("<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"{}\" \
+                 stroke-opacity=\"0.8\" marker-end=\"url(#arrowhead)\"/>\n",
+                x1, y1, x2, y2, ARROW, ARROW_WIDTH)
+    }
+}
+
+fn coordinate(x: u32, y: u32, text: &str) -> String {
+    format!("<text x=\"{}\" y=\"{}\" font-size=\"14\" font-family=\"sans-serif\" text-anchor=\"middle\" \
+             dominant-baseline=\"central\" fill=\"{}\">{}</text>\n",
+            x, y, COORDINATE, text)
+}
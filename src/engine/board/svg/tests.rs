@@ -0,0 +1,53 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::square::Square;
+use crate::engine::board::svg::SvgRenderer;
+use crate::engine::fen;
+
+fn square(id: &str) -> Square {
+    Square::from_string(id).unwrap()
+}
+
+#[test]
+fn render_snapshot() {
+    let board = fen::from_fen("8/8/8/8/8/8/1k6/K7 w - - 0 1").unwrap();
+    let mut renderer = SvgRenderer::new();
+    renderer.set_coordinates(false)
+        .set_highlight(square("a1").as_bb())
+        .add_arrow(ChessMove::new(square("a1"), square("a2")))
+        .add_arrow(ChessMove::null())
+        .add_arrow(ChessMove::new(square("b2"), square("b2")));
+
+    assert_eq!(include_str!("snapshot.svg"), renderer.render(&board));
+}
+
+#[test]
+fn render_initial_board() {
+    let svg = Board::new().to_svg();
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"400\" height=\"400\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(64 + 1, svg.matches("<rect").count());
+    assert_eq!(32 + 32, svg.matches("<text").count());
+    assert_eq!(16, svg.matches("fill=\"#ffffff\"").count());
+    // The a-file label below the board, and the white king on e1
+    assert!(svg.contains("<text x=\"42\" y=\"390\""));
+    assert!(svg.contains("<text x=\"222.5\" y=\"357.5\" font-size=\"39\" text-anchor=\"middle\" \
+                          dominant-baseline=\"central\" fill=\"#ffffff\" stroke=\"#000000\" stroke-width=\"1.5\">♚</text>"));
+    assert_eq!(svg, Board::new().to_svg());
+}
+
+#[test]
+fn render_black_orientation() {
+    let board = Board::new();
+    let e4 = board.parse_san("e4").unwrap();
+    let mut renderer = SvgRenderer::new();
+    renderer.set_orientation(Color::Black).set_highlight(e4.get_destination().as_bb()).add_arrow(e4);
+    let svg = renderer.render(&board);
+
+    // The white king is on the top, e4 is the fifth row and fourth column
+    assert!(svg.contains("<text x=\"177.5\" y=\"42.5\""));
+    assert!(svg.contains("<rect x=\"155\" y=\"155\" width=\"45\" height=\"45\" fill=\"#cdd26a\"/>"));
+    assert!(svg.contains("<line x1=\"177.5\" y1=\"87.5\" x2=\"177.5\" y2=\"155.0\""));
+}
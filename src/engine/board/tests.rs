@@ -5,6 +5,7 @@ use crate::engine::board::piece::castling::CastlingRight;
 use crate::engine::board::piece::color::Color;
 use crate::engine::board::piece::Piece;
 use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::fen;
 
 //
 // Empty board
@@ -167,7 +168,7 @@ fn get_en_passant() {
 fn in_check_empty() {
     let mut board = Board::empty();
     board.xor(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fourth).as_bb());
-    assert_eq!(false, board.in_check(Color::White));
+    assert!(!board.in_check(Color::White));
 }
 
 #[test]
@@ -175,7 +176,7 @@ fn in_check_own_queen() {
     let mut board = Board::empty();
     board.xor(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fourth).as_bb());
     board.xor(Piece::Queen, Color::White, Square::from_pos(Rank::A, File::First).as_bb());
-    assert_eq!(false, board.in_check(Color::White));
+    assert!(!board.in_check(Color::White));
 }
 
 #[test]
@@ -183,7 +184,7 @@ fn in_check_enemy_queen() {
     let mut board = Board::empty();
     board.xor(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fourth).as_bb());
     board.xor(Piece::Queen, Color::Black, Square::from_pos(Rank::A, File::First).as_bb());
-    assert_eq!(true, board.in_check(Color::White));
+    assert!(board.in_check(Color::White));
 }
 
 #[test]
@@ -268,7 +269,7 @@ fn is_valid_move_src_dst() {
     let board = Board::new();
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::A, File::First);
-    assert_eq!(false, board.is_valid_move(ChessMove::new(src, dst)));
+    assert!(!board.is_valid_move(ChessMove::new(src, dst)));
 }
 
 #[test]
@@ -277,7 +278,7 @@ fn is_valid_move_src_no_piece() {
     let board = Board::new();
     let src = Square::from_pos(Rank::C, File::First);
     let dst = Square::from_pos(Rank::D, File::First);
-    assert_eq!(false, board.is_valid_move(ChessMove::new(src, dst)));
+    assert!(!board.is_valid_move(ChessMove::new(src, dst)));
 }
 
 #[test]
@@ -286,7 +287,7 @@ fn is_valid_move_dst_own_piece() {
     let board = Board::new();
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::B, File::First);
-    assert_eq!(false, board.is_valid_move(ChessMove::new(src, dst)));
+    assert!(!board.is_valid_move(ChessMove::new(src, dst)));
 }
 
 #[test]
@@ -300,7 +301,7 @@ fn is_valid_move_dst_king() {
 
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::H, File::First);
-    assert_eq!(false, board.is_valid_move(ChessMove::new(src, dst)));
+    assert!(!board.is_valid_move(ChessMove::new(src, dst)));
 }
 
 #[test]
@@ -309,7 +310,7 @@ fn is_valid_move_valid() {
     let board = Board::new();
     let src = Square::from_pos(Rank::B, File::First);
     let dst = Square::from_pos(Rank::D, File::First);
-    assert_eq!(true, board.is_valid_move(ChessMove::new(src, dst)));
+    assert!(board.is_valid_move(ChessMove::new(src, dst)));
 }
 
 #[test]
@@ -352,7 +353,7 @@ fn knight_move() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::C, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -366,7 +367,7 @@ fn knight_capture() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::C, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -378,7 +379,7 @@ fn knight_invalid() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::C, File::Third); // invalid move
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 //
@@ -391,7 +392,7 @@ fn pawn_single_push_white() {
     let src = Square::from_pos(Rank::B, File::First);
     let dst = Square::from_pos(Rank::C, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -400,7 +401,7 @@ fn pawn_double_push_white() {
     let src = Square::from_pos(Rank::B, File::First);
     let dst = Square::from_pos(Rank::D, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -410,7 +411,7 @@ fn pawn_single_push_black() {
     let src = Square::from_pos(Rank::G, File::First);
     let dst = Square::from_pos(Rank::F, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -420,7 +421,7 @@ fn pawn_double_push_black() {
     let src = Square::from_pos(Rank::G, File::First);
     let dst = Square::from_pos(Rank::E, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -432,7 +433,7 @@ fn pawn_double_push_invalid() {
     let src = Square::from_pos(Rank::C, File::First);
     let dst = Square::from_pos(Rank::E, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -444,7 +445,7 @@ fn pawn_push_backward_invalid() {
     let src = Square::from_pos(Rank::C, File::First);
     let dst = Square::from_pos(Rank::B, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -453,7 +454,7 @@ fn pawn_attack_invalid() {
     let src = Square::from_pos(Rank::B, File::First);
     let dst = Square::from_pos(Rank::C, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -465,7 +466,7 @@ fn pawn_attack_valid_white() {
     let src = Square::from_pos(Rank::B, File::First);
     let dst = Square::from_pos(Rank::C, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -478,7 +479,7 @@ fn pawn_attack_valid_black() {
     let src = Square::from_pos(Rank::G, File::First);
     let dst = Square::from_pos(Rank::F, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 //
@@ -493,7 +494,7 @@ fn king_move() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::B, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -504,7 +505,7 @@ fn king_move_invalid() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::C, File::Third); // can only move 1 by 1
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -518,7 +519,7 @@ fn king_move_in_check() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::B, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -532,7 +533,7 @@ fn king_move_next_to_enemy_king() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::B, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 //
@@ -547,7 +548,7 @@ fn queen_move_horizontal() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::A, File::Eighth);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -558,7 +559,7 @@ fn queen_move_vertical() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::H, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -569,7 +570,7 @@ fn queen_move_diagonal() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::H, File::Eighth);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -580,7 +581,7 @@ fn queen_move_anti_diagonal() {
     let src = Square::from_pos(Rank::A, File::Eighth);
     let dst = Square::from_pos(Rank::H, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -591,7 +592,7 @@ fn queen_move_invalid() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::H, File::Second);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -605,7 +606,7 @@ fn queen_move_behind_vertical() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::G, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -619,7 +620,7 @@ fn queen_move_behind_horizontal() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::A, File::Eighth);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -633,7 +634,7 @@ fn queen_move_behind_diagonal() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::H, File::Eighth);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -647,7 +648,7 @@ fn queen_move_behind_anti_diagonal() {
     let src = Square::from_pos(Rank::A, File::Eighth);
     let dst = Square::from_pos(Rank::H, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -660,7 +661,7 @@ fn queen_capture() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::G, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(true, result);
+    assert!(result);
 }
 
 #[test]
@@ -673,7 +674,7 @@ fn queen_capture_own_piece() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::G, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 #[test]
@@ -689,7 +690,7 @@ fn queen_capture_behind() {
     let src = Square::from_pos(Rank::A, File::First);
     let dst = Square::from_pos(Rank::G, File::First);
     let result = board.is_legal_move(ChessMove::new(src, dst));
-    assert_eq!(false, result);
+    assert!(!result);
 }
 
 //
@@ -815,6 +816,31 @@ fn make_move_promotion_invalid_position() {
     assert_eq!(None, result);
 }
 
+#[test]
+fn make_move_promotion_missing() {
+    let mut board = Board::empty();
+    board.xor(Piece::Pawn, Color::White,
+              Square::from_pos(Rank::G, File::First).as_bb());
+
+    let src = Square::from_pos(Rank::G, File::First);
+    let dst = Square::from_pos(Rank::H, File::First);
+    let result = board.make_move(ChessMove::new(src, dst));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn make_move_no_promotion_before_last_rank() {
+    let mut board = Board::empty();
+    board.xor(Piece::Pawn, Color::White,
+              Square::from_pos(Rank::D, File::First).as_bb());
+
+    let src = Square::from_pos(Rank::D, File::First);
+    let dst = Square::from_pos(Rank::E, File::First);
+    let result = board.make_move(ChessMove::new(src, dst));
+    assert_ne!(None, result);
+    assert_eq!(dst.as_bb(), result.unwrap().get_pieces_color(Piece::Pawn, Color::White));
+}
+
 #[test]
 fn make_move_promotion_invalid_promoted() {
     let mut board = Board::empty();
@@ -827,3 +853,258 @@ fn make_move_promotion_invalid_promoted() {
     let result = board.make_move(ChessMove::new_promote(src, dst, promotion));
     assert_eq!(None, result);
 }
+
+//
+// Castling
+//
+
+#[test]
+fn castling_king_side() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Seventh))).unwrap();
+
+    assert_eq!(Some(Piece::King), result.piece_at(Square::from_pos(Rank::A, File::Seventh), Color::White));
+    assert_eq!(Some(Piece::Rook), result.piece_at(Square::from_pos(Rank::A, File::Sixth), Color::White));
+    assert_eq!(None, result.piece_at(Square::from_pos(Rank::A, File::Eighth), Color::White));
+    assert_eq!([CastlingRight::NoRight, CastlingRight::BothSide], result.castling_rights);
+}
+
+#[test]
+fn castling_queen_side() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::H, File::Fifth),
+        Square::from_pos(Rank::H, File::Third))).unwrap();
+
+    assert_eq!(Some(Piece::King), result.piece_at(Square::from_pos(Rank::H, File::Third), Color::Black));
+    assert_eq!(Some(Piece::Rook), result.piece_at(Square::from_pos(Rank::H, File::Fourth), Color::Black));
+    assert_eq!(None, result.piece_at(Square::from_pos(Rank::H, File::First), Color::Black));
+    assert_eq!([CastlingRight::BothSide, CastlingRight::NoRight], result.castling_rights);
+}
+
+#[test]
+fn castling_without_right() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Qkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Seventh)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn castling_path_blocked() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/RN2K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Third)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn castling_out_of_check() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/4q3/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Seventh)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn castling_through_check() {
+    let board = fen::from_fen("r3kr2/8/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Seventh)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn castling_into_check() {
+    let board = fen::from_fen("r3k1r1/8/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Seventh)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn castling_queen_side_b_file_attacked() {
+    // The rook passes the attacked b1 square, but the king does not
+    let board = fen::from_fen("1r2k3/8/8/8/8/8/8/R3K3 w Q - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::A, File::Third)));
+    assert_ne!(None, result);
+}
+
+#[test]
+fn castling_rights_king_moved() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fifth),
+        Square::from_pos(Rank::B, File::Fifth))).unwrap();
+    assert_eq!([CastlingRight::NoRight, CastlingRight::BothSide], result.castling_rights);
+}
+
+#[test]
+fn castling_rights_rook_moved() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::First),
+        Square::from_pos(Rank::B, File::First))).unwrap();
+    assert_eq!([CastlingRight::KingSide, CastlingRight::BothSide], result.castling_rights);
+}
+
+#[test]
+fn castling_rights_rook_captured() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Eighth),
+        Square::from_pos(Rank::H, File::Eighth))).unwrap();
+    assert_eq!([CastlingRight::QueenSide, CastlingRight::QueenSide], result.castling_rights);
+}
+
+//
+// En passant
+//
+
+#[test]
+fn en_passant_double_push_sets_target() {
+    let board = Board::new();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::B, File::Fifth),
+        Square::from_pos(Rank::D, File::Fifth))).unwrap();
+    assert_eq!(Some(Square::from_pos(Rank::C, File::Fifth)), result.en_passant);
+}
+
+#[test]
+fn en_passant_other_move_clears_target() {
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::H, File::Second),
+        Square::from_pos(Rank::F, File::Third))).unwrap();
+    assert_eq!(None, result.en_passant);
+}
+
+#[test]
+fn en_passant_capture() {
+    let board = fen::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::E, File::Fifth),
+        Square::from_pos(Rank::F, File::Sixth))).unwrap();
+
+    assert_eq!(Some(Piece::Pawn), result.piece_at(Square::from_pos(Rank::F, File::Sixth), Color::White));
+    assert_eq!(None, result.piece_at(Square::from_pos(Rank::E, File::Sixth), Color::Black));
+    assert_eq!(None, result.en_passant);
+}
+
+#[test]
+fn en_passant_capture_black() {
+    let board = fen::from_fen("rnbqkbnr/ppp1pppp/8/8/2Pp4/5N2/PP1PPPPP/RNBQKB1R b KQkq c3 0 3").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::D, File::Fourth),
+        Square::from_pos(Rank::C, File::Third))).unwrap();
+
+    assert_eq!(Some(Piece::Pawn), result.piece_at(Square::from_pos(Rank::C, File::Third), Color::Black));
+    assert_eq!(None, result.piece_at(Square::from_pos(Rank::D, File::Third), Color::White));
+}
+
+#[test]
+fn en_passant_capture_without_target() {
+    let board = fen::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq - 0 3").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::E, File::Fifth),
+        Square::from_pos(Rank::F, File::Sixth)));
+    assert_eq!(None, result);
+}
+
+#[test]
+fn en_passant_capture_horizontal_pin() {
+    // Capturing would remove both pawns from the rank, exposing the king to the rook
+    let board = fen::from_fen("8/8/8/K2pP2r/8/8/8/7k w - d6 0 1").unwrap();
+    let chess_move = ChessMove::new(
+        Square::from_pos(Rank::E, File::Fifth),
+        Square::from_pos(Rank::F, File::Fourth));
+    assert_eq!(None, board.make_move(chess_move));
+    assert!(!board.legal_moves().contains(&chess_move));
+}
+
+//
+// Move counters
+//
+
+#[test]
+fn half_moves_piece_move() {
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 5 10").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Second),
+        Square::from_pos(Rank::C, File::Third))).unwrap();
+    assert_eq!(6, result.half_moves);
+    assert_eq!(10, result.full_moves);
+}
+
+#[test]
+fn half_moves_pawn_move() {
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 5 10").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::B, File::Fifth),
+        Square::from_pos(Rank::D, File::Fifth))).unwrap();
+    assert_eq!(0, result.half_moves);
+}
+
+#[test]
+fn half_moves_capture() {
+    let board = fen::from_fen("4k3/8/8/3p4/8/8/8/3RK3 w - - 12 40").unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Fourth),
+        Square::from_pos(Rank::E, File::Fourth))).unwrap();
+    assert_eq!(0, result.half_moves);
+}
+
+#[test]
+fn full_moves_after_black() {
+    let board = fen::from_fen(fen::INITIAL_BOARD).unwrap();
+    let result = board.make_move(ChessMove::new(
+        Square::from_pos(Rank::A, File::Second),
+        Square::from_pos(Rank::C, File::Third))).unwrap();
+    assert_eq!(1, result.full_moves);
+    assert_eq!(1, result.half_moves);
+
+    let result = result.make_move(ChessMove::new(
+        Square::from_pos(Rank::H, File::Second),
+        Square::from_pos(Rank::F, File::Third))).unwrap();
+    assert_eq!(2, result.full_moves);
+    assert_eq!(2, result.half_moves);
+}
+
+//
+// UCI moves
+//
+
+#[test]
+fn parse_uci_legal() {
+    let board = Board::new();
+    let e2e4 = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth));
+    assert_eq!(Ok(e2e4), board.parse_uci("e2e4"));
+
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let e1g1 = ChessMove::new(Square::from_pos(Rank::A, File::Fifth), Square::from_pos(Rank::A, File::Seventh));
+    assert_eq!(Ok(e1g1), board.parse_uci("e1g1"));
+}
+
+#[test]
+fn parse_uci_illegal() {
+    use crate::engine::board::chessmove::UciMoveError;
+
+    let board = Board::new();
+    assert_eq!(Err(UciMoveError::IllegalMove("e2e5".to_owned())), board.parse_uci("e2e5"));
+    assert_eq!(Err(UciMoveError::IllegalMove("0000".to_owned())), board.parse_uci("0000"));
+    assert_eq!(Err(UciMoveError::InvalidUci("e2".to_owned())), board.parse_uci("e2"));
+
+    // The promotion piece is required when a pawn reaches the last rank
+    let board = fen::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(Err(UciMoveError::IllegalMove("a7a8".to_owned())), board.parse_uci("a7a8"));
+    assert!(board.parse_uci("a7a8q").is_ok());
+}
@@ -0,0 +1,150 @@
+//! This module implements the validation of a `Board`, to reject positions that cannot happen in a
+//! game of chess before they reach the engine.
+
+use std::fmt::{Display, Error, Formatter};
+
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::piece::{castling, color, Piece, ALL_PIECES};
+use crate::engine::board::piece::castling::CastlingRight;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::square::{constants, Rank, Square};
+
+#[cfg(test)]
+mod tests;
+
+/// Represents a violated invariant of a chess position.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum PositionError {
+    /// The player has no king.
+    MissingKing(Color),
+    /// The player has more than one king.
+    TooManyKings(Color),
+    /// There is a pawn on the first or the last rank.
+    PawnOnBackRank(Square),
+    /// These squares are occupied by both colors.
+    OverlappingColors(BitBoard),
+    /// These squares are occupied by multiple piece types.
+    OverlappingPieces(BitBoard),
+    /// These squares have a color but no piece type, or a piece type but no color.
+    InconsistentSquares(BitBoard),
+    /// The player has a castling right, but the king or the rook is not on its' initial square.
+    InvalidCastlingRight(Color, CastlingRight),
+    /// The en passant target square cannot be the result of a double pawn push.
+    InvalidEnPassant(Square),
+    /// The player who does not have to move is in check.
+    OpponentInCheck(Color),
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match *self {
+            PositionError::MissingKing(color) => write!(f, "Missing king: {}", color),
+            PositionError::TooManyKings(color) => write!(f, "Too many kings: {}", color),
+            PositionError::PawnOnBackRank(square) => write!(f, "Pawn on the first or last rank: {:?}", square),
+            PositionError::OverlappingColors(squares) => write!(f, "Squares occupied by both colors: {:?}", squares),
+            PositionError::OverlappingPieces(squares) => write!(f, "Squares occupied by multiple pieces: {:?}", squares),
+            PositionError::InconsistentSquares(squares) => write!(f, "Squares without color or piece: {:?}", squares),
+            PositionError::InvalidCastlingRight(color, side) => write!(f, "Invalid castling right: {} {:?}", color, side),
+            PositionError::InvalidEnPassant(square) => write!(f, "Invalid en passant target: {:?}", square),
+            PositionError::OpponentInCheck(color) => write!(f, "The player not to move is in check: {}", color),
+        }
+    }
+}
+
+/// Validates the board, and returns every violated invariant.
+pub fn validate(board: &Board) -> Result<(), Vec<PositionError>> {
+    let mut errors = Vec::new();
+
+    validate_bitboards(board, &mut errors);
+    validate_kings(board, &mut errors);
+    validate_pawns(board, &mut errors);
+    validate_castling_rights(board, &mut errors);
+    validate_en_passant(board, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that every occupied square has exactly one color and exactly one piece type.
+fn validate_bitboards(board: &Board, errors: &mut Vec<PositionError>) {
+    let overlapping_colors = board.colors[Color::White.to_index()] & board.colors[Color::Black.to_index()];
+    if overlapping_colors.is_not_empty() {
+        errors.push(PositionError::OverlappingColors(overlapping_colors));
+    }
+
+    let mut seen = BitBoard::empty();
+    let mut overlapping_pieces = BitBoard::empty();
+    for piece in &ALL_PIECES {
+        overlapping_pieces |= seen & board.pieces[piece.to_index()];
+        seen |= board.pieces[piece.to_index()];
+    }
+    if overlapping_pieces.is_not_empty() {
+        errors.push(PositionError::OverlappingPieces(overlapping_pieces));
+    }
+
+    let inconsistent = seen ^ board.pieces();
+    if inconsistent.is_not_empty() {
+        errors.push(PositionError::InconsistentSquares(inconsistent));
+    }
+}
+
+/// Checks that both players have exactly one king, and the player not to move is not in check.
+fn validate_kings(board: &Board, errors: &mut Vec<PositionError>) {
+    for color in &color::ALL_COLORS {
+        match board.get_pieces_color(Piece::King, *color).pop_count() {
+            0 => errors.push(PositionError::MissingKing(*color)),
+            1 => {
+                if *color != board.turn && board.in_check(*color) {
+                    errors.push(PositionError::OpponentInCheck(*color));
+                }
+            }
+            _ => errors.push(PositionError::TooManyKings(*color)),
+        }
+    }
+}
+
+/// Checks that there are no pawns on the first and the last rank.
+fn validate_pawns(board: &Board, errors: &mut Vec<PositionError>) {
+    let back_ranks = constants::RANK_A | constants::RANK_H;
+    for square in board.pieces_by_type(Piece::Pawn) & back_ranks {
+        errors.push(PositionError::PawnOnBackRank(square));
+    }
+}
+
+/// Checks that the king and the rook are on their initial squares for every castling right.
+fn validate_castling_rights(board: &Board, errors: &mut Vec<PositionError>) {
+    for color in &color::ALL_COLORS {
+        for side in &castling::CASTLING_SIDES {
+            let has_pieces = board.get_pieces_color(Piece::King, *color).is_set(castling::king_square(*color))
+                && board.get_pieces_color(Piece::Rook, *color).is_set(castling::rook_square(*color, *side));
+
+            if board.castling_rights[color.to_index()].has(*side) && !has_pieces {
+                errors.push(PositionError::InvalidCastlingRight(*color, *side));
+            }
+        }
+    }
+}
+
+/// Checks that the en passant target square is behind an enemy pawn which just made a double push.
+///
+/// The target square and the square the pawn came from must be empty.
+fn validate_en_passant(board: &Board, errors: &mut Vec<PositionError>) {
+    if let Some(target) = board.en_passant {
+        let (rank, pawn, origin) = match board.turn {
+            Color::White => (Rank::F, target.as_bb().south_one(), target.as_bb().north_one()),
+            Color::Black => (Rank::C, target.as_bb().north_one(), target.as_bb().south_one()),
+        };
+
+        let valid = target.get_rank() == rank
+            && (board.get_pieces_color(Piece::Pawn, !board.turn) & pawn).is_not_empty()
+            && (board.pieces() & (target.as_bb() | origin)).is_empty();
+
+        if !valid {
+            errors.push(PositionError::InvalidEnPassant(target));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::builder::BoardBuilder;
+use crate::engine::board::piece::castling::CastlingRight;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::board::validation::PositionError;
+use crate::engine::fen;
+
+fn validate(input: &str) -> Result<(), Vec<PositionError>> {
+    fen::from_fen(input).unwrap().validate()
+}
+
+#[test]
+fn validate_initial() {
+    assert_eq!(Ok(()), Board::new().validate());
+}
+
+#[test]
+fn validate_valid_positions() {
+    assert_eq!(Ok(()), validate("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1"));
+    assert_eq!(Ok(()), validate("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"));
+    assert_eq!(Ok(()), validate("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"));
+}
+
+#[test]
+fn validate_empty() {
+    assert_eq!(Err(vec![PositionError::MissingKing(Color::White), PositionError::MissingKing(Color::Black)]),
+               Board::empty().validate());
+}
+
+#[test]
+fn validate_too_many_kings() {
+    assert_eq!(Err(vec![PositionError::TooManyKings(Color::White)]),
+               validate("4k3/8/8/8/8/8/8/K3K3 w - - 0 1"));
+}
+
+#[test]
+fn validate_pawn_on_back_rank() {
+    assert_eq!(Err(vec![PositionError::PawnOnBackRank(Square::from_pos(Rank::A, File::First)),
+                        PositionError::PawnOnBackRank(Square::from_pos(Rank::H, File::Eighth))]),
+               validate("4k2p/8/8/8/8/8/8/P3K3 w - - 0 1"));
+}
+
+#[test]
+fn validate_opponent_in_check() {
+    assert_eq!(Err(vec![PositionError::OpponentInCheck(Color::Black)]),
+               validate("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"));
+}
+
+#[test]
+fn validate_invalid_castling_rights() {
+    assert_eq!(Err(vec![PositionError::InvalidCastlingRight(Color::White, CastlingRight::QueenSide),
+                        PositionError::InvalidCastlingRight(Color::Black, CastlingRight::KingSide),
+                        PositionError::InvalidCastlingRight(Color::Black, CastlingRight::QueenSide)]),
+               validate("r4k1r/8/8/8/8/8/8/1R2K2R w KQkq - 0 1"));
+}
+
+#[test]
+fn validate_invalid_en_passant() {
+    // Wrong rank for the side to move
+    assert_eq!(Err(vec![PositionError::InvalidEnPassant(Square::from_pos(Rank::C, File::Fifth))]),
+               validate("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e3 0 1"));
+    // No pawn in front of the target square
+    assert_eq!(Err(vec![PositionError::InvalidEnPassant(Square::from_pos(Rank::C, File::Fourth))]),
+               validate("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq d3 0 1"));
+}
+
+#[test]
+fn validate_overlapping_bitboards() {
+    let mut builder = BoardBuilder::new();
+    builder.add_piece(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fifth));
+    builder.add_piece(Piece::King, Color::Black, Square::from_pos(Rank::H, File::Fifth));
+    builder.add_piece(Piece::Rook, Color::White, Square::from_pos(Rank::D, File::Fourth));
+    builder.add_piece(Piece::Bishop, Color::Black, Square::from_pos(Rank::D, File::Fourth));
+    let square = Square::from_pos(Rank::D, File::Fourth).as_bb();
+
+    assert_eq!(Err(vec![PositionError::OverlappingColors(square), PositionError::OverlappingPieces(square)]),
+               builder.try_build().map(|_| ()));
+}
+
+#[test]
+fn validate_inconsistent_squares() {
+    let mut builder = BoardBuilder::new();
+    builder.add_piece(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fifth));
+    builder.add_piece(Piece::King, Color::Black, Square::from_pos(Rank::H, File::Fifth));
+    builder.set_color(Color::White, BitBoard::from(0x0000000000000110));
+
+    assert_eq!(Err(vec![PositionError::InconsistentSquares(BitBoard::from(0x0000000000000100))]),
+               builder.try_build().map(|_| ()));
+}
+
+#[test]
+fn try_build_valid() {
+    let mut builder = BoardBuilder::new();
+    builder.add_piece(Piece::King, Color::White, Square::from_pos(Rank::A, File::Fifth));
+    builder.add_piece(Piece::King, Color::Black, Square::from_pos(Rank::H, File::Fifth));
+    assert_eq!(Ok(builder.build()), builder.try_build());
+}
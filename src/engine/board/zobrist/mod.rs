@@ -0,0 +1,91 @@
+//! This module implements Zobrist hashing of a `Board`.
+//!
+//! Every feature of the position (a piece on a square, the side to move, a castling right, the
+//! file of the en passant target square) has a fixed random key, and the hash of the position is
+//! the XOR of the keys of its' features. Making a move only changes a few features, so the hash
+//! can be updated incrementally.
+//!
+//! The keys are the ones used by the Polyglot opening book format, so the hash of a board can be
+//! used to look up the position in a Polyglot book.
+
+use crate::engine::board::Board;
+use crate::engine::board::bitboard::BitBoard;
+use crate::engine::board::piece::{ALL_PIECES, color, Piece};
+use crate::engine::board::piece::castling::{CASTLING_SIDES, CastlingRight};
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::zobrist::random::RANDOM64;
+
+mod random;
+#[cfg(test)]
+mod tests;
+
+const CASTLING_KEYS: usize = 768;
+const EN_PASSANT_KEYS: usize = 772;
+const TURN_KEY: usize = 780;
+
+/// Returns the index of the piece in the Polyglot key array.
+///
+/// Polyglot orders the pieces as black pawn, white pawn, black knight, white knight and so on.
+fn piece_index(piece: Piece, color: Color) -> usize {
+    let kind = match piece {
+        Piece::Pawn => 0,
+        Piece::Knight => 1,
+        Piece::Bishop => 2,
+        Piece::Rook => 3,
+        Piece::Queen => 4,
+        Piece::King => 5,
+    };
+    match color {
+        Color::White => kind * 2 + 1,
+        Color::Black => kind * 2,
+    }
+}
+
+/// Returns the key of the specified piece and color on every square of the bitboard.
+pub fn piece_key(piece: Piece, color: Color, squares: BitBoard) -> u64 {
+    let offset = piece_index(piece, color) * 64;
+    squares.fold(0, |key, square| key ^ RANDOM64[offset + square.to_index() as usize])
+}
+
+/// Returns the key of the castling rights of both players.
+pub fn castling_key(castling_rights: [CastlingRight; color::NUM_COLORS]) -> u64 {
+    let mut key = 0;
+    for color in &color::ALL_COLORS {
+        for (index, side) in CASTLING_SIDES.iter().enumerate() {
+            if castling_rights[color.to_index()].has(*side) {
+                key ^= RANDOM64[CASTLING_KEYS + color.to_index() * 2 + index];
+            }
+        }
+    }
+    key
+}
+
+/// Returns the key of the en passant target square.
+///
+/// The target square is only hashed if the player who have to move can actually capture there, so
+/// positions that are the same according to the repetition rules have the same key.
+pub fn en_passant_key(board: &Board) -> u64 {
+    match board.en_passant_capture().next() {
+        Some(target) => RANDOM64[EN_PASSANT_KEYS + (target.get_file().to_index() - 1) as usize],
+        None => 0,
+    }
+}
+
+/// Returns the key of the side to move, which is only hashed when White has to move.
+pub fn side_key(turn: Color) -> u64 {
+    match turn {
+        Color::White => RANDOM64[TURN_KEY],
+        Color::Black => 0,
+    }
+}
+
+/// Computes the hash of the board from scratch.
+pub fn hash(board: &Board) -> u64 {
+    let mut key = 0;
+    for color in &color::ALL_COLORS {
+        for piece in &ALL_PIECES {
+            key ^= piece_key(*piece, *color, board.get_pieces_color(*piece, *color));
+        }
+    }
+    key ^ castling_key(board.castling_rights) ^ en_passant_key(board) ^ side_key(board.turn)
+}
@@ -0,0 +1,107 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::board::zobrist::hash;
+use crate::engine::fen;
+
+fn play(board: Board, moves: &[(Rank, File, Rank, File)]) -> Board {
+    moves.iter().fold(board, |board, (src_rank, src_file, dst_rank, dst_file)| {
+        let chess_move = ChessMove::new(Square::from_pos(*src_rank, *src_file), Square::from_pos(*dst_rank, *dst_file));
+        board.make_move(chess_move).unwrap()
+    })
+}
+
+#[test]
+fn initial_position() {
+    let board = Board::new();
+    let parsed = fen::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    assert_eq!(hash(&board), board.get_key());
+    assert_eq!(board.get_key(), parsed.get_key());
+}
+
+#[test]
+fn empty_board() {
+    let board = Board::empty();
+    assert_eq!(hash(&board), board.get_key());
+    assert_ne!(Board::new().get_key(), board.get_key());
+}
+
+#[test]
+fn move_counters_ignored() {
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let other = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 37 60").unwrap();
+    assert_eq!(board.get_key(), other.get_key());
+}
+
+#[test]
+fn side_to_move() {
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let other = fen::from_fen("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+    assert_ne!(board.get_key(), other.get_key());
+}
+
+#[test]
+fn castling_rights() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let other = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQk - 0 1").unwrap();
+    assert_ne!(board.get_key(), other.get_key());
+}
+
+#[test]
+fn en_passant_only_if_capturable() {
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let other = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(board.get_key(), other.get_key());
+
+    let board = fen::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let other = fen::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_ne!(board.get_key(), other.get_key());
+}
+
+#[test]
+fn transposition() {
+    // 1. Nf3 Nf6 2. d4 and 1. d4 Nf6 2. Nf3
+    let first = play(Board::new(), &[
+        (Rank::A, File::Seventh, Rank::C, File::Sixth),
+        (Rank::H, File::Seventh, Rank::F, File::Sixth),
+        (Rank::B, File::Fourth, Rank::D, File::Fourth),
+    ]);
+    let second = play(Board::new(), &[
+        (Rank::B, File::Fourth, Rank::D, File::Fourth),
+        (Rank::H, File::Seventh, Rank::F, File::Sixth),
+        (Rank::A, File::Seventh, Rank::C, File::Sixth),
+    ]);
+    assert_eq!(first.get_key(), second.get_key());
+}
+
+#[test]
+fn incremental_castling_and_en_passant() {
+    let board = fen::from_fen("r3k2r/8/8/8/1p6/8/P7/R3K2R w KQkq - 0 1").unwrap();
+    // a2a4 allows b4xa3 en passant, then White castles king side
+    let board = play(board, &[
+        (Rank::B, File::First, Rank::D, File::First),
+        (Rank::D, File::Second, Rank::C, File::First),
+        (Rank::A, File::Fifth, Rank::A, File::Seventh),
+    ]);
+    assert_eq!(hash(&board), board.get_key());
+    assert_eq!(fen::from_fen("r3k2r/8/8/8/8/p7/8/R4RK1 b kq - 1 2").unwrap().get_key(), board.get_key());
+}
+
+// Reference keys published with the Polyglot book format
+#[test]
+fn polyglot_reference_keys() {
+    let positions = [
+        ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 0x463B96181691FC9C),
+        ("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", 0x823C9B50FD114196),
+        ("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", 0x0756B94461C50FB0),
+        ("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2", 0x662FAFB965DB29D4),
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 0x22A48B5A8E47FF78),
+        ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3", 0x652A607CA3F242C1),
+        ("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4", 0x00FDD303C946BDD9),
+        ("rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3", 0x3C8123EA7B067637),
+        ("rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4", 0x5C3F9B829B279560),
+    ];
+    for (position, expected) in positions.iter() {
+        assert_eq!(*expected, fen::from_fen(position).unwrap().get_key(), "{}", position);
+    }
+}
@@ -0,0 +1,245 @@
+//! This module implements building Polyglot opening books from PGN game collections.
+//!
+//! Every game is replayed from the initial position up to a ply limit, and the moves played in
+//! every position are counted together with the result of the game, from the point of view of the
+//! player who made the move. Moves played less than a minimum number of times are left out of the
+//! book, the weight of the other moves is `2 * wins + draws`.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::book::{Book, decode_move, encode_move};
+use crate::engine::game::GameResult;
+use crate::engine::pgn::{PgnError, PgnReader};
+
+#[cfg(test)]
+mod tests;
+
+/// The default number of half moves replayed from every game.
+pub const DEFAULT_MAX_PLY: usize = 30;
+
+/// The default number of times a move has to be played to get into the book.
+pub const DEFAULT_MIN_COUNT: u32 = 1;
+
+/// The statistics of a move played in a position.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct MoveStats {
+    wins: u32,
+    draws: u32,
+    losses: u32,
+}
+
+impl MoveStats {
+    /// Returns the number of games won by the player who made the move.
+    pub fn get_wins(&self) -> u32 {
+        self.wins
+    }
+
+    /// Returns the number of drawn games.
+    pub fn get_draws(&self) -> u32 {
+        self.draws
+    }
+
+    /// Returns the number of games lost by the player who made the move.
+    pub fn get_losses(&self) -> u32 {
+        self.losses
+    }
+
+    /// Returns the number of games in which the move was played.
+    pub fn get_count(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Returns the weight of the move: two points for a win, one for a draw.
+    pub fn get_weight(&self) -> u32 {
+        2 * self.wins + self.draws
+    }
+
+    fn add(&mut self, result: GameResult, color: Color) {
+        match (result, color) {
+            (GameResult::WhiteWins, Color::White) | (GameResult::BlackWins, Color::Black) => self.wins += 1,
+            (GameResult::WhiteWins, Color::Black) | (GameResult::BlackWins, Color::White) => self.losses += 1,
+            (GameResult::Draw, _) => self.draws += 1,
+            (GameResult::Unknown, _) => (),
+        }
+    }
+}
+
+/// The outcome of adding a PGN game collection to a builder.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct PgnImport {
+    added: usize,
+    skipped: usize,
+    first_error: Option<PgnError>,
+}
+
+impl PgnImport {
+    /// Returns the number of games added to the builder.
+    pub fn get_added(&self) -> usize {
+        self.added
+    }
+
+    /// Returns the number of games which could not be read.
+    pub fn get_skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Returns the error of the first game which could not be read.
+    pub fn get_first_error(&self) -> Option<&PgnError> {
+        self.first_error.as_ref()
+    }
+}
+
+/// Collects the statistics of the moves played in games, and builds a book from them.
+pub struct BookBuilder {
+    max_ply: usize,
+    min_count: u32,
+    games: usize,
+    /// The statistics by the Polyglot key of the position and the encoded move.
+    positions: BTreeMap<u64, BTreeMap<u16, MoveStats>>,
+}
+
+impl Default for BookBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BookBuilder {
+    pub fn new() -> BookBuilder {
+        BookBuilder {
+            max_ply: DEFAULT_MAX_PLY,
+            min_count: DEFAULT_MIN_COUNT,
+            games: 0,
+            positions: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the number of half moves replayed from every game.
+    pub fn set_max_ply(&mut self, max_ply: usize) -> &mut BookBuilder {
+        self.max_ply = max_ply;
+        self
+    }
+
+    /// Sets the number of times a move has to be played to get into the book.
+    pub fn set_min_count(&mut self, min_count: u32) -> &mut BookBuilder {
+        self.min_count = min_count;
+        self
+    }
+
+    /// Returns the number of games added to the builder.
+    pub fn get_games(&self) -> usize {
+        self.games
+    }
+
+    /// Adds a game, played from the specified position.
+    ///
+    /// Games with unknown result are skipped, as they say nothing about the moves. The game is
+    /// only replayed until the first illegal move.
+    pub fn add_game(&mut self, board: &Board, moves: &[ChessMove], result: GameResult) {
+        if result == GameResult::Unknown {
+            return;
+        }
+
+        let mut board = *board;
+        for chess_move in moves.iter().take(self.max_ply) {
+            let next = match board.make_move(*chess_move) {
+                Some(next) => next,
+                None => break,
+            };
+
+            self.positions.entry(board.get_key())
+                .or_default()
+                .entry(encode_move(&board, *chess_move))
+                .or_default()
+                .add(result, board.get_turn());
+            board = next;
+        }
+        self.games += 1;
+    }
+
+    /// Adds every game of a PGN game collection.
+    ///
+    /// Games which cannot be read are skipped, they are counted by the returned `PgnImport`.
+    pub fn add_pgn(&mut self, pgn: &str) -> Result<PgnImport, PgnError> {
+        self.add_pgn_reader(pgn.as_bytes())
+    }
+
+    /// Adds every game read from a PGN game collection, see `add_pgn()`.
+    ///
+    /// The games are read one by one, so the collection does not have to fit into memory. Returns
+    /// an error if the input cannot be read, for example it is not valid UTF-8. The games read
+    /// before the error stay in the builder.
+    pub fn add_pgn_reader<R: BufRead>(&mut self, reader: R) -> Result<PgnImport, PgnError> {
+        let games_before = self.games;
+        let mut import = PgnImport::default();
+        for pgn_game in PgnReader::new(reader) {
+            match pgn_game {
+                Ok(pgn_game) => {
+                    let game = pgn_game.get_game();
+                    self.add_game(&game.get_initial_board(), game.get_moves(), pgn_game.get_result());
+                }
+                Err(error) if error.is_io_error() => return Err(error),
+                Err(error) => {
+                    import.skipped += 1;
+                    import.first_error.get_or_insert(error);
+                }
+            }
+        }
+        import.added = self.games - games_before;
+        Ok(import)
+    }
+
+    /// Returns the statistics of every move played in the position, including the ones which are
+    /// played less than the minimum number of times.
+    pub fn get_stats(&self, board: &Board) -> Vec<(ChessMove, MoveStats)> {
+        self.positions.get(&board.get_key())
+            .map(|moves| {
+                moves.iter()
+                    .filter_map(|(encoded, stats)| decode_move(board, *encoded).map(|chess_move| (chess_move, *stats)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Builds the book from the collected statistics.
+    ///
+    /// The entries of a position are ordered by weight, the best move first. Weights which do not
+    /// fit into 16 bits are scaled down, keeping their' proportions within the position.
+    pub fn build(&self) -> Book {
+        let mut data = Vec::new();
+        for (key, moves) in &self.positions {
+            let mut entries: Vec<(u16, u32)> = moves.iter()
+                .filter(|(_, stats)| stats.get_count() >= self.min_count)
+                .map(|(encoded, stats)| (*encoded, stats.get_weight()))
+                .collect();
+            entries.sort_by(|(_, first), (_, second)| second.cmp(first));
+
+            let max_weight = entries.first().map_or(0, |(_, weight)| u64::from(*weight));
+            for (encoded, weight) in entries {
+                let weight = if max_weight > u64::from(u16::MAX) {
+                    u64::from(weight) * u64::from(u16::MAX) / max_weight
+                } else {
+                    u64::from(weight)
+                };
+
+                data.extend_from_slice(&key.to_be_bytes());
+                data.extend_from_slice(&encoded.to_be_bytes());
+                data.extend_from_slice(&(weight as u16).to_be_bytes());
+                data.extend_from_slice(&0u32.to_be_bytes());
+            }
+        }
+        Book { data }
+    }
+}
+
+/// Builds a book from a PGN game collection, see `BookBuilder`.
+pub fn build_book(pgn: &str, max_ply: usize, min_count: u32) -> Result<Book, PgnError> {
+    let mut builder = BookBuilder::new();
+    builder.set_max_ply(max_ply).set_min_count(min_count);
+    builder.add_pgn(pgn)?;
+    Ok(builder.build())
+}
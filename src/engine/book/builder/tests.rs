@@ -0,0 +1,154 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::book::builder::{build_book, BookBuilder};
+use crate::engine::book::Book;
+use crate::engine::game::GameResult;
+
+const PGN: &str = r#"[Event "First"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 {main line} Nc6 (2... d6 3. d4) 3. Bb5 a6 $1 1-0
+
+[Event "Second"]
+[Result "1/2-1/2"]
+
+1. e4 c5 ; Sicilian
+2. Nf3 d6 1/2-1/2
+
+[Event "Third"]
+[Result "0-1"]
+
+1.d4 Nf6 2.c4 e6 0-1
+
+[Event "Unfinished"]
+
+1. e4 e5 *
+"#;
+
+fn e2e4() -> ChessMove {
+    ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth))
+}
+
+fn d2d4() -> ChessMove {
+    ChessMove::new(Square::from_pos(Rank::B, File::Fourth), Square::from_pos(Rank::D, File::Fourth))
+}
+
+#[test]
+fn add_pgn_stats() {
+    let mut builder = BookBuilder::new();
+    assert_eq!(3, builder.add_pgn(PGN).unwrap().get_added());
+    assert_eq!(3, builder.get_games());
+
+    let stats = builder.get_stats(&Board::new());
+    assert_eq!(2, stats.len());
+
+    let (_, e4) = stats.iter().find(|(chess_move, _)| *chess_move == e2e4()).unwrap();
+    assert_eq!((1, 1, 0), (e4.get_wins(), e4.get_draws(), e4.get_losses()));
+    assert_eq!(3, e4.get_weight());
+
+    let (_, d4) = stats.iter().find(|(chess_move, _)| *chess_move == d2d4()).unwrap();
+    assert_eq!((0, 0, 1), (d4.get_wins(), d4.get_draws(), d4.get_losses()));
+    assert_eq!(0, d4.get_weight());
+
+    // Black won after 1. d4, so the reply counts as a win for Black
+    let after_d4 = Board::new().make_move(d2d4()).unwrap();
+    let stats = builder.get_stats(&after_d4);
+    assert_eq!(1, stats.len());
+    assert_eq!(1, stats[0].1.get_wins());
+}
+
+#[test]
+fn add_pgn_skips_invalid_games() {
+    let pgn = format!("[Event \"Illegal\"]\n[Result \"1-0\"]\n\n1. e5 1-0\n\n{}", PGN);
+    let mut builder = BookBuilder::new();
+    let import = builder.add_pgn(&pgn).unwrap();
+    assert_eq!(3, import.get_added());
+    assert_eq!(1, import.get_skipped());
+    assert_eq!(Some("e5"), import.get_first_error().and_then(|error| error.get_san()));
+}
+
+#[test]
+fn add_pgn_reader_io_error() {
+    // The input is not valid UTF-8 after the first game
+    let mut bytes = b"[Result \"1-0\"]\n\n1. e4 1-0\n\n[Event \"".to_vec();
+    bytes.extend_from_slice(b"\xE9\"]\n");
+    let mut builder = BookBuilder::new();
+    let error = builder.add_pgn_reader(&bytes[..]).unwrap_err();
+    assert!(error.is_io_error());
+    assert_eq!(1, builder.get_games());
+}
+
+#[test]
+fn variations_are_skipped() {
+    let mut builder = BookBuilder::new();
+    builder.add_pgn(PGN).unwrap();
+
+    // After 1. e4 e5 only 2. Nf3 was played in the main line, 2... d6 was a variation
+    let board = Board::new().make_move(e2e4()).unwrap();
+    let e7e5 = ChessMove::new(Square::from_pos(Rank::G, File::Fifth), Square::from_pos(Rank::E, File::Fifth));
+    let board = board.make_move(e7e5).unwrap();
+    let g1f3 = ChessMove::new(Square::from_pos(Rank::A, File::Seventh), Square::from_pos(Rank::C, File::Sixth));
+    let board = board.make_move(g1f3).unwrap();
+
+    let stats = builder.get_stats(&board);
+    assert_eq!(1, stats.len());
+    assert_eq!(Square::from_pos(Rank::F, File::Third), stats[0].0.get_destination());
+}
+
+#[test]
+fn max_ply() {
+    let mut builder = BookBuilder::new();
+    builder.set_max_ply(1);
+    builder.add_pgn(PGN).unwrap();
+
+    let after_e4 = Board::new().make_move(e2e4()).unwrap();
+    assert_eq!(2, builder.get_stats(&Board::new()).len());
+    assert!(builder.get_stats(&after_e4).is_empty());
+}
+
+#[test]
+fn build_book_min_count() {
+    let book = build_book(PGN, 2, 2).unwrap();
+
+    // Only 1. e4 was played twice
+    assert_eq!(1, book.len());
+    let moves = book.moves(&Board::new());
+    assert_eq!(1, moves.len());
+    assert_eq!(e2e4(), moves[0].get_move());
+    assert_eq!(3, moves[0].get_weight());
+}
+
+#[test]
+fn build_round_trip() {
+    let book = build_book(PGN, 4, 1).unwrap();
+    let reread = Book::from_bytes(book.data.clone()).unwrap();
+
+    let moves = reread.moves(&Board::new());
+    assert_eq!(vec![e2e4(), d2d4()], moves.iter().map(|book_move| book_move.get_move()).collect::<Vec<_>>());
+}
+
+#[test]
+fn castling_and_scaling() {
+    let mut builder = BookBuilder::new();
+    let castles = [
+        e2e4(),
+        ChessMove::new(Square::from_pos(Rank::G, File::Fifth), Square::from_pos(Rank::E, File::Fifth)),
+        ChessMove::new(Square::from_pos(Rank::A, File::Seventh), Square::from_pos(Rank::C, File::Sixth)),
+        ChessMove::new(Square::from_pos(Rank::H, File::Second), Square::from_pos(Rank::F, File::Third)),
+        ChessMove::new(Square::from_pos(Rank::A, File::Sixth), Square::from_pos(Rank::D, File::Third)),
+        ChessMove::new(Square::from_pos(Rank::H, File::Seventh), Square::from_pos(Rank::F, File::Sixth)),
+        ChessMove::new(Square::from_pos(Rank::A, File::Fifth), Square::from_pos(Rank::A, File::Seventh)),
+    ];
+    for _ in 0..40000 {
+        builder.add_game(&Board::new(), &castles, GameResult::WhiteWins);
+    }
+    let book = builder.build();
+
+    // 80000 points are scaled down to fit into 16 bits
+    let moves = book.moves(&Board::new());
+    assert_eq!(u16::MAX, moves[0].get_weight());
+
+    let board = castles[..6].iter().fold(Board::new(), |board, chess_move| board.make_move(*chess_move).unwrap());
+    assert_eq!(castles[6], book.moves(&board)[0].get_move());
+}
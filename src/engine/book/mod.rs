@@ -0,0 +1,244 @@
+//! This module implements reading opening books in the Polyglot `.bin` format.
+//!
+//! A book is a sequence of 16 byte entries sorted by the hash key of the position, so the moves of
+//! a position can be found with binary search. Every entry is stored in big-endian byte order:
+//!
+//! * key: the Polyglot hash of the position (8 bytes), see `Board::get_key()`
+//! * move: the encoded move (2 bytes)
+//! * weight: how good the move is, relative to the other moves of the position (2 bytes)
+//! * learn: data used by learning algorithms (4 bytes)
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::{castling, Piece};
+use crate::engine::board::piece::castling::CASTLING_SIDES;
+use crate::engine::board::square::Square;
+
+pub mod builder;
+#[cfg(test)]
+mod tests;
+
+/// The size of an entry in bytes.
+pub const ENTRY_SIZE: usize = 16;
+
+/// A move of a position found in the book.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct BookMove {
+    chess_move: ChessMove,
+    weight: u16,
+    learn: u32,
+}
+
+impl BookMove {
+    /// Returns the move.
+    pub fn get_move(&self) -> ChessMove {
+        self.chess_move
+    }
+
+    /// Returns the weight of the move.
+    pub fn get_weight(&self) -> u16 {
+        self.weight
+    }
+
+    /// Returns the learning data of the move.
+    pub fn get_learn(&self) -> u32 {
+        self.learn
+    }
+}
+
+/// Represents an error which happened while loading a book.
+#[derive(Debug)]
+pub enum BookError {
+    /// The book could not be read.
+    Io(io::Error),
+    /// The size of the book (in bytes) is not a multiple of the entry size.
+    InvalidSize(usize),
+}
+
+impl Display for BookError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            BookError::Io(error) => write!(f, "Unable to read the book: {}", error),
+            BookError::InvalidSize(size) => write!(f, "Invalid book size: {} bytes is not a multiple of {}", size, ENTRY_SIZE),
+        }
+    }
+}
+
+impl Error for BookError {}
+
+impl From<io::Error> for BookError {
+    fn from(error: io::Error) -> Self {
+        BookError::Io(error)
+    }
+}
+
+/// A Polyglot opening book, loaded into memory.
+#[derive(Debug, Clone)]
+pub struct Book {
+    data: Vec<u8>,
+}
+
+impl Book {
+    /// Loads the book from the specified file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Book, BookError> {
+        Book::from_bytes(fs::read(path)?)
+    }
+
+    /// Creates a book from the content of a `.bin` file.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Book, BookError> {
+        if !data.len().is_multiple_of(ENTRY_SIZE) {
+            return Err(BookError::InvalidSize(data.len()));
+        }
+        Ok(Book { data })
+    }
+
+    /// Writes the book to the specified file in the `.bin` format.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, &self.data)
+    }
+
+    /// Returns the number of entries in the book.
+    pub fn len(&self) -> usize {
+        self.data.len() / ENTRY_SIZE
+    }
+
+    /// Checks whether the book has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns every move of the position, in the order they are stored in the book.
+    ///
+    /// Moves which are not legal in the position are skipped.
+    pub fn moves(&self, board: &Board) -> Vec<BookMove> {
+        let key = board.get_key();
+        (self.lower_bound(key)..self.len())
+            .take_while(|index| self.read(*index, 0, 8) == key)
+            .filter_map(|index| {
+                let chess_move = decode_move(board, self.read(index, 8, 2) as u16)?;
+                Some(BookMove {
+                    chess_move,
+                    weight: self.read(index, 10, 2) as u16,
+                    learn: self.read(index, 12, 4) as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// Picks a move of the position, with probability proportional to its' weight.
+    ///
+    /// The `random` number decides which move is picked. Returns `None` if the position is not in
+    /// the book, or every move has zero weight.
+    pub fn pick_move(&self, board: &Board, random: u64) -> Option<ChessMove> {
+        let moves = self.moves(board);
+        let total: u64 = moves.iter().map(|book_move| u64::from(book_move.weight)).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut remaining = random % total;
+        for book_move in moves {
+            let weight = u64::from(book_move.weight);
+            if remaining < weight {
+                return Some(book_move.chess_move);
+            }
+            remaining -= weight;
+        }
+        None
+    }
+
+    /// Picks a random move of the position, with probability proportional to its' weight.
+    ///
+    /// The `seed` is scrambled by the SplitMix64 generator, so the same seed always picks the same
+    /// move, and consecutive seeds pick independent moves.
+    pub fn random_move(&self, board: &Board, seed: u64) -> Option<ChessMove> {
+        let mut random = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        random = (random ^ (random >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        random = (random ^ (random >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        self.pick_move(board, random ^ (random >> 31))
+    }
+
+    /// Returns the index of the first entry whose key is not less than the specified key.
+    fn lower_bound(&self, key: u64) -> usize {
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.read(middle, 0, 8) < key {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+
+    /// Reads a big-endian number of `size` bytes at the `offset` of the specified entry.
+    fn read(&self, index: usize, offset: usize, size: usize) -> u64 {
+        let start = index * ENTRY_SIZE + offset;
+        self.data[start..start + size].iter().fold(0, |value, byte| value << 8 | u64::from(*byte))
+    }
+}
+
+/// Encodes a legal move of the position in the Polyglot encoding, see `decode_move()`.
+pub fn encode_move(board: &Board, chess_move: ChessMove) -> u16 {
+    let color = board.get_turn();
+    let src = chess_move.get_source();
+    let dst = match castling::castling_side(color, src, chess_move.get_destination()) {
+        Some(side) if board.piece_at(src, color) == Some(Piece::King) => castling::rook_square(color, side),
+        _ => chess_move.get_destination(),
+    };
+    let promotion = match chess_move.get_promotion() {
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(Piece::Queen) => 4,
+        _ => 0,
+    };
+
+    dst.to_index() as u16 | (src.to_index() as u16) << 6 | promotion << 12
+}
+
+/// Decodes a move in the Polyglot encoding, and returns it if it is legal in the position.
+///
+/// The bits of the move from the least significant one are the destination file and rank, the
+/// source file and rank (3 bits each), and the promotion piece (knight, bishop, rook or queen from
+/// 1 to 4). Castling is encoded as the king capturing its' own rook.
+pub fn decode_move(board: &Board, encoded: u16) -> Option<ChessMove> {
+    let dst = Square::new((encoded & 0x3F) as u8);
+    let src = Square::new(((encoded >> 6) & 0x3F) as u8);
+    let promotion = match (encoded >> 12) & 0x7 {
+        0 => None,
+        1 => Some(Piece::Knight),
+        2 => Some(Piece::Bishop),
+        3 => Some(Piece::Rook),
+        4 => Some(Piece::Queen),
+        _ => return None,
+    };
+
+    let color = board.get_turn();
+    let castling = if board.piece_at(src, color) == Some(Piece::King) && src == castling::king_square(color) {
+        CASTLING_SIDES.iter().find(|side| castling::rook_square(color, **side) == dst)
+    } else {
+        None
+    };
+
+    let chess_move = match (castling, promotion) {
+        (Some(side), _) => ChessMove::new(src, castling::king_destination(color, *side)),
+        (None, Some(piece)) => ChessMove::new_promote(src, dst, piece),
+        (None, None) => ChessMove::new(src, dst),
+    };
+
+    if board.legal_moves().contains(&chess_move) {
+        Some(chess_move)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,105 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::book::{Book, BookError, decode_move};
+use crate::engine::fen;
+
+fn book() -> Book {
+    Book::from_bytes(include_bytes!("test.bin").to_vec()).unwrap()
+}
+
+fn e2e4() -> ChessMove {
+    ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth))
+}
+
+fn d2d4() -> ChessMove {
+    ChessMove::new(Square::from_pos(Rank::B, File::Fourth), Square::from_pos(Rank::D, File::Fourth))
+}
+
+#[test]
+fn invalid_size() {
+    match Book::from_bytes(vec![0; 17]) {
+        Err(BookError::InvalidSize(17)) => (),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn open_missing_file() {
+    assert!(matches!(Book::open("missing.bin"), Err(BookError::Io(_))));
+}
+
+#[test]
+fn initial_position() {
+    let book = book();
+    assert_eq!(8, book.len());
+
+    // The illegal e2e5 entry is skipped
+    let moves = book.moves(&Board::new());
+    assert_eq!(2, moves.len());
+    assert_eq!(e2e4(), moves[0].get_move());
+    assert_eq!(3, moves[0].get_weight());
+    assert_eq!(d2d4(), moves[1].get_move());
+    assert_eq!(1, moves[1].get_weight());
+}
+
+#[test]
+fn position_not_in_book() {
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert!(book().moves(&board).is_empty());
+    assert_eq!(None, book().pick_move(&board, 0));
+}
+
+#[test]
+fn pick_move_by_weight() {
+    let book = book();
+    let board = Board::new();
+    assert_eq!(Some(e2e4()), book.pick_move(&board, 0));
+    assert_eq!(Some(e2e4()), book.pick_move(&board, 2));
+    assert_eq!(Some(d2d4()), book.pick_move(&board, 3));
+    assert_eq!(Some(e2e4()), book.pick_move(&board, 4));
+
+    for seed in 0..16 {
+        let random = book.random_move(&board, seed).unwrap();
+        assert!(random == e2e4() || random == d2d4());
+        assert_eq!(Some(random), book.random_move(&board, seed));
+    }
+}
+
+#[test]
+fn zero_weight_not_picked() {
+    let book = book();
+    let board = Board::new().make_move(e2e4()).unwrap();
+    let c7c5 = ChessMove::new(Square::from_pos(Rank::G, File::Third), Square::from_pos(Rank::E, File::Third));
+
+    assert_eq!(2, book.moves(&board).len());
+    for random in 0..4 {
+        assert_eq!(Some(c7c5), book.pick_move(&board, random));
+    }
+}
+
+#[test]
+fn castling() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let moves = book().moves(&board);
+    let e1 = Square::from_pos(Rank::A, File::Fifth);
+
+    assert_eq!(2, moves.len());
+    assert_eq!(ChessMove::new(e1, Square::from_pos(Rank::A, File::Seventh)), moves[0].get_move());
+    assert_eq!(ChessMove::new(e1, Square::from_pos(Rank::A, File::Third)), moves[1].get_move());
+}
+
+#[test]
+fn promotion() {
+    let board = fen::from_fen("8/P7/8/8/8/8/k7/4K3 w - - 0 1").unwrap();
+    let a7a8q = ChessMove::new_promote(Square::from_pos(Rank::G, File::First), Square::from_pos(Rank::H, File::First), Piece::Queen);
+    assert_eq!(Some(a7a8q), book().pick_move(&board, 0));
+}
+
+#[test]
+fn decode_king_move_to_rook_without_rights() {
+    // Without castling rights, e1h1 is not castling, and is not legal either
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/4K2R w - - 0 1").unwrap();
+    assert_eq!(None, decode_move(&board, 0x0107));
+}
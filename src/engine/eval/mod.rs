@@ -0,0 +1,125 @@
+//! This module implements the static evaluation of a position.
+//!
+//! The evaluation is the material of both sides with piece-square tables, which reward pieces
+//! standing on good squares, like knights in the center or pawns close to promotion.
+
+use crate::engine::board::Board;
+use crate::engine::board::piece::ALL_PIECES;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::Square;
+
+#[cfg(test)]
+mod tests;
+
+/// The piece-square tables are written from White's side: the first row is the 8th rank.
+#[rustfmt::skip]
+const PAWN_TABLE: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+    50,  50,  50,  50,  50,  50,  50,  50,
+    10,  10,  20,  30,  30,  20,  10,  10,
+     5,   5,  10,  25,  25,  10,   5,   5,
+     0,   0,   0,  20,  20,   0,   0,   0,
+     5,  -5, -10,   0,   0, -10,  -5,   5,
+     5,  10,  10, -20, -20,  10,  10,   5,
+     0,   0,   0,   0,   0,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const KNIGHT_TABLE: [i32; 64] = [
+    -50, -40, -30, -30, -30, -30, -40, -50,
+    -40, -20,   0,   0,   0,   0, -20, -40,
+    -30,   0,  10,  15,  15,  10,   0, -30,
+    -30,   5,  15,  20,  20,  15,   5, -30,
+    -30,   0,  15,  20,  20,  15,   0, -30,
+    -30,   5,  10,  15,  15,  10,   5, -30,
+    -40, -20,   0,   5,   5,   0, -20, -40,
+    -50, -40, -30, -30, -30, -30, -40, -50,
+];
+
+#[rustfmt::skip]
+const BISHOP_TABLE: [i32; 64] = [
+    -20, -10, -10, -10, -10, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,  10,  10,   5,   0, -10,
+    -10,   5,   5,  10,  10,   5,   5, -10,
+    -10,   0,  10,  10,  10,  10,   0, -10,
+    -10,  10,  10,  10,  10,  10,  10, -10,
+    -10,   5,   0,   0,   0,   0,   5, -10,
+    -20, -10, -10, -10, -10, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const ROOK_TABLE: [i32; 64] = [
+     0,   0,   0,   0,   0,   0,   0,   0,
+     5,  10,  10,  10,  10,  10,  10,   5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+    -5,   0,   0,   0,   0,   0,   0,  -5,
+     0,   0,   0,   5,   5,   0,   0,   0,
+];
+
+#[rustfmt::skip]
+const QUEEN_TABLE: [i32; 64] = [
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+    -10,   0,   0,   0,   0,   0,   0, -10,
+    -10,   0,   5,   5,   5,   5,   0, -10,
+     -5,   0,   5,   5,   5,   5,   0,  -5,
+      0,   0,   5,   5,   5,   5,   0,  -5,
+    -10,   5,   5,   5,   5,   5,   0, -10,
+    -10,   0,   5,   0,   0,   0,   0, -10,
+    -20, -10, -10,  -5,  -5, -10, -10, -20,
+];
+
+#[rustfmt::skip]
+const KING_TABLE: [i32; 64] = [
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -30, -40, -40, -50, -50, -40, -40, -30,
+    -20, -30, -30, -40, -40, -30, -30, -20,
+    -10, -20, -20, -20, -20, -20, -20, -10,
+     20,  20,   0,   0,   0,   0,  20,  20,
+     20,  30,  10,   0,   0,  10,  30,  20,
+];
+
+/// Evaluates the position in centipawns, from the point of view of the player who have to move.
+pub fn evaluate(board: &Board) -> i32 {
+    let score = evaluate_color(board, Color::White) - evaluate_color(board, Color::Black);
+    match board.get_turn() {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+/// Returns the material and the piece-square bonuses of the color's pieces.
+fn evaluate_color(board: &Board, color: Color) -> i32 {
+    let mut score = 0;
+    for piece in &ALL_PIECES {
+        for square in board.get_pieces_color(*piece, color) {
+            score += piece.get_value() + square_bonus(*piece, color, square);
+        }
+    }
+    score
+}
+
+/// Returns the bonus of the piece-square table for the piece of the color on the square.
+pub fn square_bonus(piece: Piece, color: Color, square: Square) -> i32 {
+    let index = square.to_index() as usize;
+    // The tables start with the 8th rank, Black's pieces use the mirrored square
+    let index = match color {
+        Color::White => index ^ 56,
+        Color::Black => index,
+    };
+
+    match piece {
+        Piece::Pawn => PAWN_TABLE[index],
+        Piece::Knight => KNIGHT_TABLE[index],
+        Piece::Bishop => BISHOP_TABLE[index],
+        Piece::Rook => ROOK_TABLE[index],
+        Piece::Queen => QUEEN_TABLE[index],
+        Piece::King => KING_TABLE[index],
+    }
+}
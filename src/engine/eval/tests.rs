@@ -0,0 +1,32 @@
+use crate::engine::board::Board;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::Square;
+use crate::engine::eval;
+use crate::engine::fen;
+
+#[test]
+fn initial_position_is_balanced() {
+    assert_eq!(0, eval::evaluate(&Board::new()));
+
+    let board = Board::new().make_move(Board::new().parse_san("e4").unwrap()).unwrap();
+    assert_eq!(-eval::evaluate(&fen::from_fen(&board.to_fen().replace(" b ", " w ")).unwrap()), eval::evaluate(&board));
+}
+
+#[test]
+fn material() {
+    // White is a rook up, the score is from the side to move's point of view
+    let white = fen::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    let black = fen::from_fen("4k3/8/8/8/8/8/8/R3K3 b - - 0 1").unwrap();
+    assert!(eval::evaluate(&white) > 400);
+    assert_eq!(-eval::evaluate(&white), eval::evaluate(&black));
+}
+
+#[test]
+fn square_bonus_is_mirrored() {
+    let e4 = Square::from_string("e4").unwrap();
+    let e5 = Square::from_string("e5").unwrap();
+    let a1 = Square::from_string("a1").unwrap();
+    assert_eq!(eval::square_bonus(Piece::Pawn, Color::White, e4), eval::square_bonus(Piece::Pawn, Color::Black, e5));
+    assert!(eval::square_bonus(Piece::Knight, Color::White, e4) > eval::square_bonus(Piece::Knight, Color::White, a1));
+}
@@ -0,0 +1,421 @@
+//! This module implements the parsing and writing of `Extended Position Description` records.
+//!
+//! An EPD record contains the first four fields of FEN, the piece placement, the active color, the
+//! castling availability and the en passant target square, followed by operations. Every operation
+//! is an opcode followed by operands, and terminated by a semicolon. Test suites use opcodes like
+//! `bm` for the best moves or `id` for the name of the position:
+//!
+//! ```text
+//! 2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";
+//! ```
+//!
+//! The halfmove clock and the fullmove number can be set with the `hmvc` and `fmvn` opcodes. Like
+//! in perft suites, they can also be written as two numbers after the four fields.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::fen::{
+    build_board, parse_castling_rights, parse_en_passant, parse_field, parse_full_moves,
+    parse_half_moves, parse_pieces, parse_side_to_move, split_fields, write_castling_rights,
+    write_pieces, CASTLING_FIELD, EN_PASSANT_FIELD, FENParseError, FENParseErrorKind, PIECES_FIELD,
+    SIDE_TO_MOVE_FIELD,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The number of position fields in an EPD record.
+const NUM_FIELDS: usize = 4;
+
+/// An operation of an EPD record, with typed operands.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Operation {
+    /// `am`: moves which should not be played.
+    AvoidMoves(Vec<ChessMove>),
+    /// `bm`: the best moves of the position.
+    BestMoves(Vec<ChessMove>),
+    /// `id`: the name of the position.
+    Id(String),
+    /// `c0`..`c9`: a comment, with the index of the opcode.
+    Comment(u8, String),
+    /// `acd`: the depth of the analysis.
+    AnalysisDepth(u32),
+    /// `ce`: the evaluation in centipawns, from the side to move's point of view.
+    CentipawnEvaluation(i32),
+    /// `pv`: the predicted variation, starting from the position.
+    PredictedVariation(Vec<ChessMove>),
+    /// `D1`..`D9`: the expected perft node count of the depth.
+    Perft(u8, u64),
+    /// An opcode which has no typed representation, with its' operands as written.
+    Other(String, Vec<String>),
+}
+
+impl Operation {
+    /// Returns the opcode of the operation.
+    pub fn get_opcode(&self) -> String {
+        match self {
+            Operation::AvoidMoves(_) => "am".to_owned(),
+            Operation::BestMoves(_) => "bm".to_owned(),
+            Operation::Id(_) => "id".to_owned(),
+            Operation::Comment(index, _) => format!("c{}", index),
+            Operation::AnalysisDepth(_) => "acd".to_owned(),
+            Operation::CentipawnEvaluation(_) => "ce".to_owned(),
+            Operation::PredictedVariation(_) => "pv".to_owned(),
+            Operation::Perft(depth, _) => format!("D{}", depth),
+            Operation::Other(opcode, _) => opcode.clone(),
+        }
+    }
+
+    /// Returns the operands of the operation as written in the record, moves in SAN.
+    fn write_operands(&self, board: &Board) -> Vec<String> {
+        match self {
+            Operation::AvoidMoves(moves) | Operation::BestMoves(moves) => moves.iter()
+                .map(|&chess_move| board.to_san(chess_move))
+                .collect(),
+            Operation::PredictedVariation(moves) => {
+                let mut board = *board;
+                moves.iter()
+                    .map(|&chess_move| {
+                        let san = board.to_san(chess_move);
+                        board = board.make_move(chess_move).unwrap_or(board);
+                        san
+                    })
+                    .collect()
+            }
+            Operation::Id(value) | Operation::Comment(_, value) => vec![format!("\"{}\"", value)],
+            Operation::AnalysisDepth(depth) => vec![depth.to_string()],
+            Operation::CentipawnEvaluation(score) => vec![score.to_string()],
+            Operation::Perft(_, nodes) => vec![nodes.to_string()],
+            Operation::Other(_, operands) => operands.clone(),
+        }
+    }
+}
+
+/// A position with its' operations.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Epd {
+    board: Board,
+    operations: Vec<Operation>,
+}
+
+impl Epd {
+    /// Creates a record of the position without operations.
+    pub fn new(board: Board) -> Epd {
+        Epd {
+            board,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Returns the position.
+    pub fn get_board(&self) -> Board {
+        self.board
+    }
+
+    /// Returns the operations, in the order they were written.
+    pub fn get_operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Returns the operation with the specified opcode.
+    pub fn get_operation(&self, opcode: &str) -> Option<&Operation> {
+        self.operations.iter().find(|operation| operation.get_opcode() == opcode)
+    }
+
+    /// Returns the best moves of the `bm` operation, or an empty slice if there are none.
+    pub fn get_best_moves(&self) -> &[ChessMove] {
+        match self.get_operation("bm") {
+            Some(Operation::BestMoves(moves)) => moves,
+            _ => &[],
+        }
+    }
+
+    /// Returns the moves to avoid of the `am` operation, or an empty slice if there are none.
+    pub fn get_avoid_moves(&self) -> &[ChessMove] {
+        match self.get_operation("am") {
+            Some(Operation::AvoidMoves(moves)) => moves,
+            _ => &[],
+        }
+    }
+
+    /// Returns the name of the position of the `id` operation.
+    pub fn get_id(&self) -> Option<&str> {
+        match self.get_operation("id") {
+            Some(Operation::Id(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Returns the expected perft node counts as `(depth, nodes)` pairs.
+    pub fn get_perft(&self) -> Vec<(u8, u64)> {
+        self.operations.iter()
+            .filter_map(|operation| match *operation {
+                Operation::Perft(depth, nodes) => Some((depth, nodes)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Adds the operation, replacing the previous operation with the same opcode.
+    pub fn set_operation(&mut self, operation: Operation) -> &mut Self {
+        let opcode = operation.get_opcode();
+        match self.operations.iter_mut().find(|current| current.get_opcode() == opcode) {
+            Some(current) => *current = operation,
+            None => self.operations.push(operation),
+        }
+        self
+    }
+}
+
+impl Display for Epd {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", to_epd(self))
+    }
+}
+
+/// Represents errors happened during the EPD parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum EpdParseError {
+    /// One of the position fields is invalid.
+    Position(FENParseError),
+    /// An operation is invalid, with a message and the offset of the operation in the input.
+    Operation(String, usize),
+}
+
+impl Display for EpdParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            EpdParseError::Position(error) => write!(f, "{}", error),
+            EpdParseError::Operation(msg, offset) => write!(f, "{} (character {})", msg, offset),
+        }
+    }
+}
+
+impl Error for EpdParseError {}
+
+impl From<FENParseError> for EpdParseError {
+    fn from(error: FENParseError) -> Self {
+        EpdParseError::Position(error)
+    }
+}
+
+/// An operation before its' operands are parsed.
+struct RawOperation {
+    opcode: String,
+    operands: Vec<String>,
+    /// The character offset of the opcode in the input.
+    offset: usize,
+}
+
+/// Parse an EPD record.
+///
+/// Moves are written in SAN, and resolved against the position, so an illegal move is an error.
+pub fn from_epd(input: &str) -> Result<Epd, EpdParseError> {
+    // The position and the clocks end at the first semicolon, like in `0 1;D1 20`
+    let head = input.split(';').next().unwrap_or("");
+    let fields = split_fields(head);
+    if fields.len() < NUM_FIELDS {
+        let msg = format!("Missing field, expected {} fields but found {}", NUM_FIELDS, fields.len());
+        let kind = FENParseErrorKind::FENMissingField(msg);
+        return Err(FENParseError::new(kind, fields.len(), input.chars().count()).into());
+    }
+
+    let pieces = parse_field(&fields, PIECES_FIELD, parse_pieces)?;
+    let side_to_move = parse_field(&fields, SIDE_TO_MOVE_FIELD, parse_side_to_move)?;
+    let castling_rights = parse_field(&fields, CASTLING_FIELD, parse_castling_rights)?;
+    let en_passant = parse_field(&fields, EN_PASSANT_FIELD, parse_en_passant)?;
+
+    // The clocks of perft suites, written like in FEN
+    let mut rest = &fields[NUM_FIELDS..];
+    let mut half_moves = 0;
+    let mut full_moves = 1;
+    if rest.len() >= 2 && rest.iter().take(2).all(|(_, field)| field.starts_with(|c: char| c.is_ascii_digit())) {
+        half_moves = parse_field(rest, 0, parse_half_moves)?;
+        full_moves = parse_field(rest, 1, parse_full_moves)?;
+        rest = &rest[2..];
+    }
+
+    let start = rest.first().map_or(head.chars().count(), |&(offset, _)| offset);
+    let operations = split_operations(&input.chars().skip(start).collect::<String>(), start)?;
+
+    for operation in &operations {
+        match operation.opcode.as_str() {
+            "hmvc" => half_moves = parse_number(operation)?,
+            "fmvn" => full_moves = parse_number(operation)?,
+            _ => {}
+        }
+    }
+
+    let board = build_board(pieces, side_to_move, castling_rights, en_passant, half_moves, full_moves);
+    let operations = operations.iter()
+        .filter(|operation| operation.opcode != "hmvc" && operation.opcode != "fmvn")
+        .map(|operation| parse_operation(&board, operation))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Epd {
+        board,
+        operations,
+    })
+}
+
+/// Convert an EPD record to a string.
+///
+/// The halfmove clock and the fullmove number are written as `hmvc` and `fmvn` operations, unless
+/// they have their' default values.
+pub fn to_epd(epd: &Epd) -> String {
+    let board = &epd.board;
+    let mut result = format!("{} {} {} {}",
+                             write_pieces(board),
+                             board.get_turn(),
+                             write_castling_rights(board.get_castling_rights()),
+                             board.get_en_passant().map_or("-".to_owned(), |square| square.to_string()));
+
+    if board.get_half_moves() != 0 {
+        result.push_str(&format!(" hmvc {};", board.get_half_moves()));
+    }
+    if board.get_full_moves() != 1 {
+        result.push_str(&format!(" fmvn {};", board.get_full_moves()));
+    }
+    for operation in &epd.operations {
+        result.push(' ');
+        result.push_str(&operation.get_opcode());
+        for operand in operation.write_operands(board) {
+            result.push(' ');
+            result.push_str(&operand);
+        }
+        result.push(';');
+    }
+
+    result
+}
+
+/// Splits the operations into opcodes and operands. Operands can be strings in double quotes, which
+/// may contain whitespace and semicolons.
+fn split_operations(input: &str, start: usize) -> Result<Vec<RawOperation>, EpdParseError> {
+    let mut operations = Vec::new();
+    let mut tokens: Vec<(usize, String)> = Vec::new();
+    let mut chars = input.chars().enumerate().peekable();
+
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            ';' => {
+                if let Some(operation) = to_operation(std::mem::take(&mut tokens)) {
+                    operations.push(operation);
+                }
+            }
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, ch)) => value.push(ch),
+                        None => {
+                            let msg = "Unterminated string operand".to_owned();
+                            return Err(EpdParseError::Operation(msg, start + offset));
+                        }
+                    }
+                }
+                tokens.push((start + offset, format!("\"{}\"", value)));
+            }
+            ch if ch.is_whitespace() => {}
+            ch => {
+                let mut token = ch.to_string();
+                while let Some(&(_, next)) = chars.peek() {
+                    if next.is_whitespace() || next == ';' || next == '"' {
+                        break;
+                    }
+                    token.push(next);
+                    chars.next();
+                }
+                tokens.push((start + offset, token));
+            }
+        }
+    }
+
+    // The last operation of perft suites is often not terminated
+    operations.extend(to_operation(tokens));
+    Ok(operations)
+}
+
+fn to_operation(tokens: Vec<(usize, String)>) -> Option<RawOperation> {
+    let mut tokens = tokens.into_iter();
+    let (offset, opcode) = tokens.next()?;
+    Some(RawOperation {
+        opcode,
+        operands: tokens.map(|(_, operand)| operand).collect(),
+        offset,
+    })
+}
+
+fn parse_operation(board: &Board, operation: &RawOperation) -> Result<Operation, EpdParseError> {
+    let opcode = operation.opcode.as_str();
+    let is_opcode = opcode.starts_with(|c: char| c.is_ascii_alphabetic())
+        && opcode.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_opcode {
+        return Err(operation_error(operation, format!("Invalid opcode: {}", opcode)));
+    }
+
+    let result = match opcode {
+        "am" => Operation::AvoidMoves(parse_moves(board, operation)?),
+        "bm" => Operation::BestMoves(parse_moves(board, operation)?),
+        "id" => Operation::Id(parse_string(operation)?),
+        "acd" => Operation::AnalysisDepth(parse_number(operation)?),
+        "ce" => Operation::CentipawnEvaluation(parse_number(operation)?),
+        "pv" => Operation::PredictedVariation(parse_variation(board, operation)?),
+        _ => match (&opcode[..1], opcode[1..].parse::<u8>()) {
+            ("c", Ok(index)) if opcode.len() == 2 => Operation::Comment(index, parse_string(operation)?),
+            ("D", Ok(depth)) if depth > 0 => Operation::Perft(depth, parse_number(operation)?),
+            _ => Operation::Other(operation.opcode.clone(), operation.operands.clone()),
+        },
+    };
+
+    Ok(result)
+}
+
+fn parse_moves(board: &Board, operation: &RawOperation) -> Result<Vec<ChessMove>, EpdParseError> {
+    if operation.operands.is_empty() {
+        return Err(operation_error(operation, format!("Missing moves of {}", operation.opcode)));
+    }
+
+    operation.operands.iter()
+        .map(|san| board.parse_san(san).map_err(|error| operation_error(operation, error.to_string())))
+        .collect()
+}
+
+fn parse_variation(board: &Board, operation: &RawOperation) -> Result<Vec<ChessMove>, EpdParseError> {
+    let mut board = *board;
+    let mut moves = Vec::new();
+
+    for san in &operation.operands {
+        let chess_move = board.parse_san(san).map_err(|error| operation_error(operation, error.to_string()))?;
+        board = board.make_move(chess_move).unwrap();
+        moves.push(chess_move);
+    }
+
+    Ok(moves)
+}
+
+fn parse_string(operation: &RawOperation) -> Result<String, EpdParseError> {
+    match operation.operands.as_slice() {
+        [operand] if operand.starts_with('"') => Ok(operand[1..operand.len() - 1].to_owned()),
+        // Unquoted strings are accepted, if they are a single word
+        [operand] => Ok(operand.clone()),
+        _ => Err(operation_error(operation, format!("Expected a single string operand for {}", operation.opcode))),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(operation: &RawOperation) -> Result<T, EpdParseError> {
+    match operation.operands.as_slice() {
+        [operand] => operand.parse()
+            .map_err(|_| operation_error(operation, format!("Unable to parse number of {}: {}", operation.opcode, operand))),
+        _ => Err(operation_error(operation, format!("Expected a single number operand for {}", operation.opcode))),
+    }
+}
+
+fn operation_error(operation: &RawOperation, msg: String) -> EpdParseError {
+    EpdParseError::Operation(msg, operation.offset)
+}
@@ -0,0 +1,89 @@
+use crate::engine::board::square::Square;
+use crate::engine::fen;
+use crate::engine::fen::epd::{Epd, EpdParseError, Operation};
+use crate::engine::fen::FENParseErrorKind;
+
+#[test]
+fn parse_test_suite_record() {
+    let epd = fen::epd::from_epd("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id \"WAC.001\";").unwrap();
+    assert_eq!("2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1", epd.get_board().to_fen());
+    assert_eq!(Some("WAC.001"), epd.get_id());
+    assert_eq!(1, epd.get_best_moves().len());
+    assert_eq!(Square::from_string("g6"), Some(epd.get_best_moves()[0].get_destination()));
+    assert!(epd.get_avoid_moves().is_empty());
+}
+
+#[test]
+fn parse_operations() {
+    let input = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - \
+        am Ng5 Bb5; bm Nc3 Bc4 d4; c0 \"Two knights; or Italian\"; acd 12; ce -15; pv Bc4 Nf6; \
+        hmvc 2; fmvn 3; xyz a b;";
+    let epd = fen::epd::from_epd(input).unwrap();
+    let board = epd.get_board();
+
+    assert_eq!(2, board.get_half_moves());
+    assert_eq!(3, board.get_full_moves());
+    assert_eq!(2, epd.get_avoid_moves().len());
+    assert_eq!(3, epd.get_best_moves().len());
+
+    let operations = epd.get_operations();
+    assert_eq!(7, operations.len());
+    assert_eq!(Operation::Comment(0, "Two knights; or Italian".to_owned()), operations[2]);
+    assert_eq!(Operation::AnalysisDepth(12), operations[3]);
+    assert_eq!(Operation::CentipawnEvaluation(-15), operations[4]);
+    assert_eq!(Operation::PredictedVariation(vec![board.parse_san("Bc4").unwrap(),
+                                                  board.make_move(board.parse_san("Bc4").unwrap()).unwrap().parse_san("Nf6").unwrap()]),
+               operations[5]);
+    assert_eq!(Operation::Other("xyz".to_owned(), vec!["a".to_owned(), "b".to_owned()]), operations[6]);
+    assert_eq!(None, epd.get_operation("hmvc"));
+}
+
+#[test]
+fn parse_perft_record() {
+    let epd = fen::epd::from_epd("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ;D1 20 ;D2 400").unwrap();
+    assert_eq!(fen::INITIAL_BOARD, epd.get_board().to_fen());
+    assert_eq!(vec![(1, 20), (2, 400)], epd.get_perft());
+
+    // The clocks may end at the semicolon of the first operation
+    let epd = fen::epd::from_epd("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1;D1 20;D2 400").unwrap();
+    assert_eq!(fen::INITIAL_BOARD, epd.get_board().to_fen());
+    assert_eq!(vec![(1, 20), (2, 400)], epd.get_perft());
+}
+
+#[test]
+fn parse_errors() {
+    let error = fen::epd::from_epd("8/8/8/8/8/8/8/K6k w -").unwrap_err();
+    match error {
+        EpdParseError::Position(error) => assert!(matches!(error.get_kind(), FENParseErrorKind::FENMissingField(_))),
+        _ => panic!("Unexpected error: {}", error),
+    }
+
+    assert_eq!(EpdParseError::Operation("Illegal move: Ka3".to_owned(), 24),
+               fen::epd::from_epd("8/8/8/8/8/8/8/K6k w - - bm Ka3;").unwrap_err());
+    assert!(matches!(fen::epd::from_epd("8/8/8/8/8/8/8/K6k w - - id \"x;").unwrap_err(), EpdParseError::Operation(_, 27)));
+    assert!(matches!(fen::epd::from_epd("8/8/8/8/8/8/8/K6k w - - acd x;").unwrap_err(), EpdParseError::Operation(_, 24)));
+    assert_eq!(1, fen::epd::from_epd("8/8/8/8/8/8/8/K6k w - - bm Kb2").unwrap().get_best_moves().len());
+    assert!(matches!(fen::epd::from_epd("8/8/8/8/8/8/8/K6k w - - 1bm Kb2;").unwrap_err(), EpdParseError::Operation(_, 24)));
+}
+
+#[test]
+fn write_record() {
+    let input = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - hmvc 2; fmvn 3; \
+        bm Nc3 Bc4; id \"Open game\"; ce 20; pv Bb5 a6; D1 27;";
+    let epd = fen::epd::from_epd(input).unwrap();
+    assert_eq!(input, fen::epd::to_epd(&epd));
+    assert_eq!(epd, fen::epd::from_epd(&epd.to_string()).unwrap());
+}
+
+#[test]
+fn set_operation() {
+    let mut epd = Epd::new(fen::from_fen(fen::INITIAL_BOARD).unwrap());
+    let e4 = epd.get_board().parse_san("e4").unwrap();
+    let d4 = epd.get_board().parse_san("d4").unwrap();
+    epd.set_operation(Operation::BestMoves(vec![e4]))
+        .set_operation(Operation::Id("start".to_owned()))
+        .set_operation(Operation::BestMoves(vec![d4]));
+
+    assert_eq!(&[d4], epd.get_best_moves());
+    assert_eq!("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - bm d4; id \"start\";", epd.to_string());
+}
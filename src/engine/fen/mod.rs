@@ -1,5 +1,5 @@
 //! This module implements the parsing of a `Forsyth–Edwards Notation` formatted string,
-//! and converting it to a `Board` representation.
+//! and converting it to a `Board` representation, as well as writing a `Board` back as FEN.
 //!
 //! Forsyth–Edwards Notation (FEN) is a standard notation for describing a particular board position of a chess game. The purpose of FEN is to provide all the necessary information to restart a game from a particular position.
 //!
@@ -20,6 +20,10 @@
 //! rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1
 //! ```
 
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
 use regex::Regex;
 
 use crate::engine::board::Board;
@@ -29,15 +33,43 @@ use crate::engine::board::piece::castling::CastlingRight;
 use crate::engine::board::piece::color::Color;
 use crate::engine::board::square::{File, Rank, Square};
 
+pub mod epd;
+
 #[cfg(test)]
 mod tests;
 
 /// The FEN representation of the initial board.
 pub const INITIAL_BOARD: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
+/// The index of the piece placement field.
+const PIECES_FIELD: usize = 0;
+/// The index of the active color field.
+const SIDE_TO_MOVE_FIELD: usize = 1;
+/// The index of the castling availability field.
+const CASTLING_FIELD: usize = 2;
+/// The index of the en passant target square field.
+const EN_PASSANT_FIELD: usize = 3;
+/// The index of the halfmove clock field.
+const HALF_MOVE_FIELD: usize = 4;
+/// The index of the fullmove number field.
+const FULL_MOVE_FIELD: usize = 5;
+/// The number of fields in a complete FEN record.
+const NUM_FIELDS: usize = 6;
+
 /// Represents parser errors happened during the FEN parsing.
+///
+/// Besides the kind of the error, it contains the index of the FEN field and the offset of the
+/// character in the input where the error happened. Both of them start from `0`.
+#[derive(Debug, Eq, PartialEq)]
+pub struct FENParseError {
+    kind: FENParseErrorKind,
+    field: usize,
+    offset: usize,
+}
+
+/// The kind of the parser error, with a message describing the problem.
 #[derive(Debug, Eq, PartialEq)]
-pub enum FENParseError {
+pub enum FENParseErrorKind {
     FENPiece(String),
     FENSideToMove(String),
     FENPieceType(String),
@@ -46,8 +78,70 @@ pub enum FENParseError {
     FENFullMove(String),
     FENEnPassant(String),
     FENCastlingAbility(String),
+    FENMissingField(String),
+    FENTooManyFields(String),
+}
+
+impl FENParseError {
+    /// Creates a new parser error.
+    pub fn new(kind: FENParseErrorKind, field: usize, offset: usize) -> FENParseError {
+        FENParseError {
+            kind,
+            field,
+            offset,
+        }
+    }
+
+    /// Returns the kind of the error.
+    pub fn get_kind(&self) -> &FENParseErrorKind {
+        &self.kind
+    }
+
+    /// Returns the index of the field in which the error happened.
+    pub fn get_field(&self) -> usize {
+        self.field
+    }
+
+    /// Returns the offset of the character in the input at which the error happened.
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Moves the offset of the error by `start` characters.
+    ///
+    /// Field parsers report offsets relative to the field, this converts them to input offsets.
+    fn shift(self, start: usize) -> FENParseError {
+        FENParseError::new(self.kind, self.field, self.offset + start)
+    }
+}
+
+impl Display for FENParseErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let message = match self {
+            FENParseErrorKind::FENPiece(msg)
+            | FENParseErrorKind::FENSideToMove(msg)
+            | FENParseErrorKind::FENPieceType(msg)
+            | FENParseErrorKind::FENPieceColor(msg)
+            | FENParseErrorKind::FENHalfMove(msg)
+            | FENParseErrorKind::FENFullMove(msg)
+            | FENParseErrorKind::FENEnPassant(msg)
+            | FENParseErrorKind::FENCastlingAbility(msg)
+            | FENParseErrorKind::FENMissingField(msg)
+            | FENParseErrorKind::FENTooManyFields(msg) => msg,
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl Display for FENParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{} (field {}, character {})", self.kind, self.field, self.offset)
+    }
 }
 
+impl Error for FENParseError {}
+
 struct FENPiece {
     piece_type: Piece,
     color: Color,
@@ -55,35 +149,183 @@ struct FENPiece {
 }
 
 /// Parse a FEN string and produce a Board.
+///
+/// The halfmove clock and the fullmove number can be omitted together, in that case they default
+/// to `0` and `1`, like in EPD records.
 pub fn from_fen(input: &str) -> Result<Board, FENParseError> {
-    let parts: Vec<&str> = input.trim().split_whitespace().collect();
-    let pieces = parse_pieces(parts[0])?;
-    let side_to_move = parse_side_to_move(parts[1])?;
-    let catling_rights = parse_castling_rights(parts[2])?;
-    let en_passant = parse_en_passant(parts[3])?;
-    let half_moves = parse_half_moves(parts[4])?;
-    let full_moves = parse_full_moves(parts[5])?;
+    let fields = split_fields(input);
+    let input_length = input.chars().count();
+
+    if fields.len() < HALF_MOVE_FIELD {
+        let msg = format!("Missing field, expected {} fields but found {}", NUM_FIELDS, fields.len());
+        return Err(FENParseError::new(FENParseErrorKind::FENMissingField(msg), fields.len(), input_length));
+    } else if fields.len() == FULL_MOVE_FIELD {
+        let msg = "Missing fullmove number after the halfmove clock".to_owned();
+        return Err(FENParseError::new(FENParseErrorKind::FENMissingField(msg), FULL_MOVE_FIELD, input_length));
+    } else if fields.len() > NUM_FIELDS {
+        let msg = format!("Too many fields, expected {} fields but found {}", NUM_FIELDS, fields.len());
+        let offset = fields[NUM_FIELDS].0;
+        return Err(FENParseError::new(FENParseErrorKind::FENTooManyFields(msg), NUM_FIELDS, offset));
+    }
+
+    let pieces = parse_field(&fields, PIECES_FIELD, parse_pieces)?;
+    let side_to_move = parse_field(&fields, SIDE_TO_MOVE_FIELD, parse_side_to_move)?;
+    let catling_rights = parse_field(&fields, CASTLING_FIELD, parse_castling_rights)?;
+    let en_passant = parse_field(&fields, EN_PASSANT_FIELD, parse_en_passant)?;
+    let (half_moves, full_moves) = if fields.len() == NUM_FIELDS {
+        (parse_field(&fields, HALF_MOVE_FIELD, parse_half_moves)?,
+         parse_field(&fields, FULL_MOVE_FIELD, parse_full_moves)?)
+    } else {
+        (0, 1)
+    };
+
+    Ok(build_board(pieces, side_to_move, catling_rights, en_passant, half_moves, full_moves))
+}
+
+/// Convert a Board to a FEN string.
+pub fn to_fen(board: &Board) -> String {
+    format!("{} {} {} {} {} {}",
+            write_pieces(board),
+            board.get_turn(),
+            write_castling_rights(board.get_castling_rights()),
+            board.get_en_passant().map_or("-".to_owned(), |square| square.to_string()),
+            board.get_half_moves(),
+            board.get_full_moves())
+}
+
+fn build_board(pieces: Vec<FENPiece>,
+               side_to_move: Color,
+               castling_rights: [CastlingRight; color::NUM_COLORS],
+               en_passant: Option<Square>,
+               half_moves: u16,
+               full_moves: u16) -> Board {
     let mut board_builder = BoardBuilder::new();
     board_builder.set_turn(side_to_move);
     board_builder.set_half_moves(half_moves);
     board_builder.set_full_moves(full_moves);
     board_builder.set_en_passant(en_passant);
-    board_builder.set_castling_rights(catling_rights);
+    board_builder.set_castling_rights(castling_rights);
 
     for piece in pieces {
         board_builder.add_piece(piece.piece_type, piece.color, piece.square);
     }
 
-    Ok(board_builder.build())
+    board_builder.build()
+}
+
+fn write_pieces(board: &Board) -> String {
+    let mut ranks = Vec::new();
+
+    for rank in (1..=8).rev() {
+        let mut result = String::new();
+        let mut empty = 0;
+
+        for file in 1..=8 {
+            let square = Square::from_pos(Rank::from_index(rank).unwrap(), File::from_index(file).unwrap());
+            match write_piece(board, square) {
+                Some(piece) => {
+                    if empty > 0 {
+                        result.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    result.push_str(&piece);
+                }
+                None => empty += 1,
+            }
+        }
+
+        if empty > 0 {
+            result.push_str(&empty.to_string());
+        }
+        ranks.push(result);
+    }
+
+    ranks.join("/")
+}
+
+fn write_piece(board: &Board, square: Square) -> Option<String> {
+    if let Some(piece) = board.piece_at(square, Color::White) {
+        Some(piece.to_string())
+    } else {
+        board.piece_at(square, Color::Black).map(|piece| piece.to_string().to_ascii_lowercase())
+    }
+}
+
+fn write_castling_rights(castling_rights: [CastlingRight; color::NUM_COLORS]) -> String {
+    let white = castling_rights[Color::White.to_index()];
+    let black = castling_rights[Color::Black.to_index()];
+    let mut result = String::new();
+
+    if white.has_king_side() {
+        result.push('K');
+    }
+    if white.has_queen_side() {
+        result.push('Q');
+    }
+    if black.has_king_side() {
+        result.push('k');
+    }
+    if black.has_queen_side() {
+        result.push('q');
+    }
+
+    if result.is_empty() {
+        "-".to_owned()
+    } else {
+        result
+    }
+}
+
+/// Splits the input to whitespace separated fields, keeping the character offset of every field.
+fn split_fields(input: &str) -> Vec<(usize, &str)> {
+    let mut fields = Vec::new();
+    let mut start = None;
+
+    for (offset, (index, ch)) in input.char_indices().enumerate() {
+        match (start, ch.is_whitespace()) {
+            (None, false) => start = Some((offset, index)),
+            (Some((field_offset, field_index)), true) => {
+                fields.push((field_offset, &input[field_index..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((field_offset, field_index)) = start {
+        fields.push((field_offset, &input[field_index..]));
+    }
+
+    fields
+}
+
+/// Parses a single field with the `parser`, converting the error offset to an input offset.
+fn parse_field<T>(fields: &[(usize, &str)],
+                  index: usize,
+                  parser: fn(&str) -> Result<T, FENParseError>) -> Result<T, FENParseError> {
+    let (start, field) = fields[index];
+    parser(field).map_err(|error| error.shift(start))
 }
 
 fn parse_pieces(input: &str) -> Result<Vec<FENPiece>, FENParseError> {
-    let ranks: Vec<&str> = input.split("/").collect();
+    let ranks: Vec<&str> = input.split('/').collect();
     let mut pieces = Vec::new();
+    let mut offset = 0;
+
+    for (i, rank) in ranks.iter().enumerate() {
+        if i >= 8 {
+            let msg = format!("Too many ranks: {}", input);
+            return Err(FENParseError::new(FENParseErrorKind::FENPiece(msg), PIECES_FIELD, offset - 1));
+        }
 
-    for i in 0..ranks.len() {
-        let mut parsed = parse_piece(ranks[i], 8 - i as u8)?;
+        let mut parsed = parse_piece(rank, 8 - i as u8).map_err(|error| error.shift(offset))?;
         pieces.append(&mut parsed);
+        offset += rank.chars().count() + 1;
+    }
+
+    if ranks.len() < 8 {
+        let msg = format!("Expected 8 ranks, found {}: {}", ranks.len(), input);
+        return Err(FENParseError::new(FENParseErrorKind::FENPiece(msg), PIECES_FIELD, input.chars().count()));
     }
 
     Ok(pieces)
@@ -96,16 +338,16 @@ fn parse_piece(rank: &str, rank_num: u8) -> Result<Vec<FENPiece>, FENParseError>
     let is_number = Regex::new(r"^[1-8]$").unwrap();
     let is_piece = Regex::new(r"^[PNBRQKpnbrqk]$").unwrap();
 
-    for ch in rank.chars() {
+    for (offset, ch) in rank.chars().enumerate() {
         let ch_str = ch.to_string();
         if is_number.is_match(&ch_str) {
             let skip: u8 = ch_str.parse().unwrap();
             file += skip;
         } else if is_piece.is_match(&ch_str) {
-            let piece_type = parse_piece_type(&ch_str)?;
-            let color = parse_piece_color(&ch_str)?;
+            let piece_type = parse_piece_type(&ch_str).map_err(|error| error.shift(offset))?;
+            let color = parse_piece_color(&ch_str).map_err(|error| error.shift(offset))?;
             let r = Rank::from_index(rank_num).unwrap();
-            let f = File::from_index(file).unwrap();
+            let f = File::from_index(file).ok_or_else(|| too_many_files(rank, offset))?;
             pieces.push(FENPiece {
                 piece_type,
                 color,
@@ -113,13 +355,28 @@ fn parse_piece(rank: &str, rank_num: u8) -> Result<Vec<FENPiece>, FENParseError>
             });
             file += 1;
         } else {
-            return Err(FENParseError::FENPiece(format!("Unable to parse rank: {}", rank)));
+            let msg = format!("Unable to parse rank: {}", rank);
+            return Err(FENParseError::new(FENParseErrorKind::FENPiece(msg), PIECES_FIELD, offset));
         }
+
+        if file > 9 {
+            return Err(too_many_files(rank, offset));
+        }
+    }
+
+    if file < 9 {
+        let msg = format!("Rank describes only {} files: {}", file - 1, rank);
+        return Err(FENParseError::new(FENParseErrorKind::FENPiece(msg), PIECES_FIELD, rank.chars().count()));
     }
 
     Ok(pieces)
 }
 
+fn too_many_files(rank: &str, offset: usize) -> FENParseError {
+    let msg = format!("Rank describes more than 8 files: {}", rank);
+    FENParseError::new(FENParseErrorKind::FENPiece(msg), PIECES_FIELD, offset)
+}
+
 fn parse_piece_type(input: &str) -> Result<Piece, FENParseError> {
     match input.to_ascii_lowercase().trim() {
         "p" => Ok(Piece::Pawn),
@@ -128,7 +385,10 @@ fn parse_piece_type(input: &str) -> Result<Piece, FENParseError> {
         "r" => Ok(Piece::Rook),
         "q" => Ok(Piece::Queen),
         "k" => Ok(Piece::King),
-        _ => Err(FENParseError::FENPieceType(format!("Unknown piece type: {}", input)))
+        _ => {
+            let msg = format!("Unknown piece type: {}", input);
+            Err(FENParseError::new(FENParseErrorKind::FENPieceType(msg), PIECES_FIELD, 0))
+        }
     }
 }
 
@@ -141,7 +401,8 @@ fn parse_piece_color(input: &str) -> Result<Color, FENParseError> {
     } else if is_black_piece.is_match(input) {
         Ok(Color::Black)
     } else {
-        Err(FENParseError::FENPieceColor(format!("Unknown color: {}", input)))
+        let msg = format!("Unknown color: {}", input);
+        Err(FENParseError::new(FENParseErrorKind::FENPieceColor(msg), PIECES_FIELD, 0))
     }
 }
 
@@ -151,7 +412,7 @@ fn parse_side_to_move(input: &str) -> Result<Color, FENParseError> {
         "b" => Ok(Color::Black),
         _ => {
             let msg = format!("Unable to parse side to chessmove: {}", input);
-            Err(FENParseError::FENSideToMove(msg))
+            Err(FENParseError::new(FENParseErrorKind::FENSideToMove(msg), SIDE_TO_MOVE_FIELD, 0))
         }
     }
 }
@@ -159,20 +420,24 @@ fn parse_side_to_move(input: &str) -> Result<Color, FENParseError> {
 fn parse_half_moves(input: &str) -> Result<u16, FENParseError> {
     let regex = Regex::new("^\\d+$").unwrap();
 
-    if regex.is_match(input) {
-        Ok(input.parse().unwrap())
-    } else {
-        Err(FENParseError::FENHalfMove(format!("Unable to parse half chessmove clock: {}", input)))
+    match input.parse() {
+        Ok(half_moves) if regex.is_match(input) => Ok(half_moves),
+        _ => {
+            let msg = format!("Unable to parse half chessmove clock: {}", input);
+            Err(FENParseError::new(FENParseErrorKind::FENHalfMove(msg), HALF_MOVE_FIELD, 0))
+        }
     }
 }
 
 fn parse_full_moves(input: &str) -> Result<u16, FENParseError> {
     let regex = Regex::new("^\\d+$").unwrap();
 
-    if regex.is_match(input) {
-        Ok(input.parse().unwrap())
-    } else {
-        Err(FENParseError::FENFullMove(format!("Unable to parse full chessmove clock: {}", input)))
+    match input.parse() {
+        Ok(full_moves) if regex.is_match(input) => Ok(full_moves),
+        _ => {
+            let msg = format!("Unable to parse full chessmove clock: {}", input);
+            Err(FENParseError::new(FENParseErrorKind::FENFullMove(msg), FULL_MOVE_FIELD, 0))
+        }
     }
 }
 
@@ -182,15 +447,11 @@ fn parse_en_passant(input: &str) -> Result<Option<Square>, FENParseError> {
     if regex.is_match(input) {
         match input {
             "-" => Ok(None),
-            ss => {
-                let chars: Vec<char> = ss.chars().collect();
-                let rank = Rank::from_string(&chars.get(0).unwrap().to_string()).unwrap();
-                let file = File::from_index(chars.get(1).unwrap().to_string().parse().unwrap()).unwrap();
-                Ok(Some(Square::from_pos(rank, file)))
-            }
+            ss => Ok(Square::from_string(ss)),
         }
     } else {
-        Err(FENParseError::FENEnPassant(format!("Unable to parse en passant target: {}", input)))
+        let msg = format!("Unable to parse en passant target: {}", input);
+        Err(FENParseError::new(FENParseErrorKind::FENEnPassant(msg), EN_PASSANT_FIELD, 0))
     }
 }
 
@@ -216,6 +477,7 @@ fn parse_castling_rights(input: &str) -> Result<[CastlingRight; color::NUM_COLOR
         result[Color::Black.to_index()] = black;
         Ok(result)
     } else {
-        Err(FENParseError::FENCastlingAbility(format!("Unable to parse castling ability: {}", input)))
+        let msg = format!("Unable to parse castling ability: {}", input);
+        Err(FENParseError::new(FENParseErrorKind::FENCastlingAbility(msg), CASTLING_FIELD, 0))
     }
 }
@@ -1,3 +1,5 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
 use crate::engine::board::piece::Piece;
 use crate::engine::board::square::{Rank, File, Square};
 use crate::engine::fen;
@@ -74,7 +76,7 @@ fn parse_castling_rights_error() {
 #[test]
 fn parse_en_passant() {
     let result = super::parse_en_passant("e6").unwrap();
-    assert_eq!(Some(Square::from_pos(Rank::E, File::Sixth)), result);
+    assert_eq!(Some(Square::from_pos(Rank::F, File::Fifth)), result);
 }
 
 #[test]
@@ -135,4 +137,144 @@ fn from_fen() {
 #[should_panic]
 fn from_fen_error() {
     super::from_fen("invalid/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
-}
\ No newline at end of file
+}
+
+#[test]
+fn parse_en_passant_black() {
+    let result = super::parse_en_passant("a3").unwrap();
+    assert_eq!(Some(Square::from_pos(Rank::C, File::First)), result);
+}
+
+#[test]
+fn to_fen_initial() {
+    assert_eq!(fen::INITIAL_BOARD, super::to_fen(&Board::new()));
+}
+
+#[test]
+fn to_fen_empty() {
+    assert_eq!("8/8/8/8/8/8/8/8 w - - 0 0", super::to_fen(&Board::empty()));
+}
+
+#[test]
+fn to_fen_round_trip() {
+    let positions = [
+        fen::INITIAL_BOARD,
+        "7k/8/8/8/8/8/8/8 b - - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kq e3 0 1",
+    ];
+
+    for position in positions.iter() {
+        let board = super::from_fen(position).unwrap();
+        assert_eq!(*position, super::to_fen(&board));
+        assert_eq!(*position, board.to_fen());
+    }
+}
+
+#[test]
+fn to_fen_after_moves() {
+    let board = Board::new().make_move(ChessMove::new(
+        Square::from_pos(Rank::B, File::Fifth),
+        Square::from_pos(Rank::D, File::Fifth))).unwrap();
+    assert_eq!("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", board.to_fen());
+}
+
+#[test]
+fn from_fen_without_move_counters() {
+    let board = super::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -").unwrap();
+    assert_eq!(0, board.get_half_moves());
+    assert_eq!(1, board.get_full_moves());
+}
+
+#[test]
+fn from_fen_empty_input() {
+    let error = super::from_fen("").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(0, error.get_offset());
+}
+
+#[test]
+fn from_fen_truncated() {
+    let error = super::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w").unwrap_err();
+    assert_eq!(2, error.get_field());
+    assert_eq!(45, error.get_offset());
+}
+
+#[test]
+fn from_fen_missing_full_moves() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8 w - - 0").unwrap_err();
+    assert_eq!(5, error.get_field());
+}
+
+#[test]
+fn from_fen_too_many_fields() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8 w - - 0 1 x").unwrap_err();
+    assert_eq!(6, error.get_field());
+    assert_eq!(26, error.get_offset());
+}
+
+#[test]
+fn from_fen_rank_too_long() {
+    let error = super::from_fen("8/8/8/8/4k4/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(10, error.get_offset());
+}
+
+#[test]
+fn from_fen_rank_too_long_piece() {
+    let error = super::from_fen("8/8/8/8/8/8/8/RNBQKBNRR w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(22, error.get_offset());
+}
+
+#[test]
+fn from_fen_rank_too_short() {
+    let error = super::from_fen("8/8/7/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(5, error.get_offset());
+}
+
+#[test]
+fn from_fen_too_many_ranks() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(15, error.get_offset());
+}
+
+#[test]
+fn from_fen_too_few_ranks() {
+    let error = super::from_fen("8/8/8/8/8/8/8 w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(13, error.get_offset());
+}
+
+#[test]
+fn from_fen_invalid_character() {
+    let error = super::from_fen("8/8/8/8/8/8/8/7x w - - 0 1").unwrap_err();
+    assert_eq!(0, error.get_field());
+    assert_eq!(15, error.get_offset());
+}
+
+#[test]
+fn from_fen_invalid_field_offset() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8  w  KQx - 0 1").unwrap_err();
+    assert_eq!(2, error.get_field());
+    assert_eq!(20, error.get_offset());
+}
+
+#[test]
+fn from_fen_half_moves_overflow() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8 w - - 99999999 1").unwrap_err();
+    assert_eq!(4, error.get_field());
+    assert_eq!(22, error.get_offset());
+}
+
+#[test]
+fn from_fen_error_display() {
+    let error = super::from_fen("8/8/8/8/8/8/8/8 z - - 0 1").unwrap_err();
+    assert_eq!("Unable to parse side to chessmove: z (field 1, character 16)", error.to_string());
+}
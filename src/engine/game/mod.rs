@@ -0,0 +1,171 @@
+//! This module implements a `Game`, which records how the game got to the current position.
+//!
+//! The game starts from an initial `Board`, and keeps the history of the moves made, so moves can
+//! be undone, and the repetition of positions can be detected.
+
+use std::fmt::{Display, Error, Formatter};
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::status::GameStatus;
+
+#[cfg(test)]
+mod tests;
+
+/// The result of a game, as it is written in PGN.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameResult {
+    /// White won the game: `1-0`.
+    WhiteWins,
+    /// Black won the game: `0-1`.
+    BlackWins,
+    /// The game is drawn: `1/2-1/2`.
+    Draw,
+    /// The game is still in progress, or the result is unknown: `*`.
+    Unknown,
+}
+
+impl GameResult {
+    /// Converts a PGN result token to a `GameResult`.
+    pub fn from_string(result: &str) -> Option<GameResult> {
+        match result.trim() {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None
+        }
+    }
+}
+
+impl Display for GameResult {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let result = match *self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unknown => "*",
+        };
+
+        write!(f, "{}", result)
+    }
+}
+
+/// A game of chess, with its' initial position and move history.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Game {
+    moves: Vec<ChessMove>,
+    /// The position after every ply, the first one is the initial position.
+    positions: Vec<Board>,
+}
+
+impl Default for Game {
+    /// Returns a game from the initial position, see `Game::new()`.
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    /// Creates a new game from the initial position.
+    pub fn new() -> Game {
+        Game::from_board(Board::new())
+    }
+
+    /// Creates a new game starting from the specified position.
+    pub fn from_board(board: Board) -> Game {
+        Game {
+            moves: Vec::new(),
+            positions: vec![board],
+        }
+    }
+
+    /// Returns the position the game started from.
+    pub fn get_initial_board(&self) -> Board {
+        self.positions[0]
+    }
+
+    /// Returns the current position.
+    pub fn get_board(&self) -> Board {
+        self.positions[self.positions.len() - 1]
+    }
+
+    /// Returns the moves made since the initial position.
+    pub fn get_moves(&self) -> &[ChessMove] {
+        &self.moves
+    }
+
+    /// Returns the number of half moves made since the initial position.
+    pub fn get_ply(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns the position after the specified number of half moves, or `None` if the game is shorter.
+    ///
+    /// Ply `0` is the initial position.
+    pub fn position_at(&self, ply: usize) -> Option<Board> {
+        self.positions.get(ply).copied()
+    }
+
+    /// Validates the move and makes it, if it is valid.
+    ///
+    /// Returns the new position after the move, or `None` if the move was invalid.
+    pub fn push(&mut self, chess_move: ChessMove) -> Option<Board> {
+        let board = self.get_board().make_move(chess_move)?;
+        self.moves.push(chess_move);
+        self.positions.push(board);
+        Some(board)
+    }
+
+    /// Undoes the last move.
+    ///
+    /// Returns the undone move, or `None` if the game is at the initial position.
+    pub fn pop(&mut self) -> Option<ChessMove> {
+        let chess_move = self.moves.pop()?;
+        self.positions.pop();
+        Some(chess_move)
+    }
+
+    /// Counts how many times the current position occurred in the game, including the current one.
+    ///
+    /// Only the positions since the last capture or pawn move are compared, as those moves cannot
+    /// be undone.
+    pub fn repetition_count(&self) -> usize {
+        let board = self.get_board();
+        let reversible = (board.get_half_moves() as usize).min(self.positions.len() - 1);
+
+        self.positions.iter()
+            .rev()
+            .take(reversible + 1)
+            .step_by(2)
+            .filter(|position| position.get_key() == board.get_key() && position.is_same_position(&board))
+            .count()
+    }
+
+    /// Checks whether the current position occurred at least three times, so a draw can be claimed.
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    /// Checks whether the current position occurred at least five times, so the game is drawn.
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetition_count() >= 5
+    }
+
+    /// Returns the status of the game, including the repetition rules.
+    pub fn status(&self) -> GameStatus {
+        let status = self.get_board().status();
+        match status {
+            GameStatus::Ongoing | GameStatus::FiftyMoveRule => {
+                if self.is_fivefold_repetition() {
+                    GameStatus::FivefoldRepetition
+                } else if self.is_threefold_repetition() {
+                    GameStatus::ThreefoldRepetition
+                } else {
+                    status
+                }
+            }
+            _ => status,
+        }
+    }
+}
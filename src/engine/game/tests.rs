@@ -0,0 +1,123 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::status::GameStatus;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::fen;
+use crate::engine::game::Game;
+
+fn knight_moves() -> [ChessMove; 4] {
+    [
+        ChessMove::new(Square::from_pos(Rank::A, File::Seventh), Square::from_pos(Rank::C, File::Sixth)),
+        ChessMove::new(Square::from_pos(Rank::H, File::Seventh), Square::from_pos(Rank::F, File::Sixth)),
+        ChessMove::new(Square::from_pos(Rank::C, File::Sixth), Square::from_pos(Rank::A, File::Seventh)),
+        ChessMove::new(Square::from_pos(Rank::F, File::Sixth), Square::from_pos(Rank::H, File::Seventh)),
+    ]
+}
+
+#[test]
+fn new() {
+    let game = Game::new();
+    assert_eq!(Board::new(), game.get_board());
+    assert_eq!(Board::new(), game.get_initial_board());
+    assert_eq!(0, game.get_ply());
+    assert_eq!(1, game.repetition_count());
+}
+
+#[test]
+fn push_and_pop() {
+    let mut game = Game::new();
+    let e4 = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth));
+    let after = game.push(e4).unwrap();
+
+    assert_eq!(after, game.get_board());
+    assert_eq!(&[e4], game.get_moves());
+    assert_eq!(Some(Board::new()), game.position_at(0));
+    assert_eq!(Some(after), game.position_at(1));
+    assert_eq!(None, game.position_at(2));
+
+    assert_eq!(Some(e4), game.pop());
+    assert_eq!(Board::new(), game.get_board());
+    assert_eq!(None, game.pop());
+}
+
+#[test]
+fn push_illegal() {
+    let mut game = Game::new();
+    let illegal = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::E, File::Fifth));
+    assert_eq!(None, game.push(illegal));
+    assert_eq!(0, game.get_ply());
+}
+
+#[test]
+fn threefold_repetition() {
+    let mut game = Game::new();
+
+    for chess_move in knight_moves().iter() {
+        game.push(*chess_move).unwrap();
+    }
+    assert_eq!(2, game.repetition_count());
+    assert!(!game.is_threefold_repetition());
+
+    for chess_move in knight_moves().iter() {
+        game.push(*chess_move).unwrap();
+    }
+    assert_eq!(3, game.repetition_count());
+    assert!(game.is_threefold_repetition());
+    assert!(!game.is_fivefold_repetition());
+    assert_eq!(GameStatus::ThreefoldRepetition, game.status());
+
+    game.pop();
+    assert_eq!(2, game.repetition_count());
+    assert_eq!(GameStatus::Ongoing, game.status());
+}
+
+#[test]
+fn fivefold_repetition() {
+    let mut game = Game::new();
+    for _ in 0..4 {
+        for chess_move in knight_moves().iter() {
+            game.push(*chess_move).unwrap();
+        }
+    }
+    assert_eq!(5, game.repetition_count());
+    assert_eq!(GameStatus::FivefoldRepetition, game.status());
+    assert!(game.status().is_game_over());
+}
+
+#[test]
+fn repetition_ignores_move_counters() {
+    let board = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let other = fen::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 12 30").unwrap();
+    assert!(board.is_same_position(&other));
+}
+
+#[test]
+fn repetition_en_passant_without_capture() {
+    // No black pawn can capture on e3, so the target square does not matter
+    let board = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let other = fen::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert!(board.is_same_position(&other));
+
+    // The pawn on d4 can capture on e3
+    let board = fen::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+    let other = fen::from_fen("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert!(!board.is_same_position(&other));
+}
+
+#[test]
+fn repetition_castling_rights() {
+    let board = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let other = fen::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1").unwrap();
+    assert!(!board.is_same_position(&other));
+}
+
+
+#[test]
+fn game_result() {
+    use crate::engine::game::GameResult;
+
+    for result in ["1-0", "0-1", "1/2-1/2", "*"].iter() {
+        assert_eq!(*result, GameResult::from_string(result).unwrap().to_string());
+    }
+    assert_eq!(None, GameResult::from_string("2-0"));
+}
@@ -1,2 +1,8 @@
 pub mod board;
-pub mod fen;
\ No newline at end of file
+pub mod book;
+pub mod eval;
+pub mod fen;
+pub mod game;
+pub mod perft;
+pub mod pgn;
+pub mod search;
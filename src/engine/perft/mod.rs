@@ -0,0 +1,189 @@
+//! This module implements [Perft](https://www.chessprogramming.org/Perft), a debugging function
+//! that walks the move generation tree and counts the leaf nodes, to verify the move generator.
+//!
+//! The module can also read and run EPD-style perft suites, where every line contains a position
+//! followed by the expected node counts per depth:
+//!
+//! ```text
+//! rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ;D1 20 ;D2 400 ;D3 8902
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::fen::epd;
+use crate::engine::fen::epd::Operation;
+
+#[cfg(test)]
+mod tests;
+
+/// Counts the leaf nodes of the legal move tree of the specified `depth`.
+pub fn perft(board: &Board, depth: u8) -> u64 {
+    match depth {
+        0 => 1,
+        // Bulk counting: the number of legal moves is the number of leaf nodes
+        1 => board.legal_moves().len() as u64,
+        _ => board.legal_moves()
+            .into_iter()
+            .map(|chess_move| perft(&board.make_move(chess_move).unwrap(), depth - 1))
+            .sum()
+    }
+}
+
+/// Counts the leaf nodes of the legal move tree of the specified `depth` for every root move.
+///
+/// At depth `0` no move is made, so the result is empty.
+pub fn divide(board: &Board, depth: u8) -> Vec<(ChessMove, u64)> {
+    if depth == 0 {
+        return Vec::new();
+    }
+
+    board.legal_moves()
+        .into_iter()
+        .map(|chess_move| (chess_move, perft(&board.make_move(chess_move).unwrap(), depth - 1)))
+        .collect()
+}
+
+/// A position of a perft suite with the expected node counts.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PerftEntry {
+    fen: String,
+    board: Board,
+    expected: Vec<(u8, u64)>,
+}
+
+impl PerftEntry {
+    /// Returns the FEN of the position.
+    pub fn get_fen(&self) -> &str {
+        &self.fen
+    }
+
+    /// Returns the position.
+    pub fn get_board(&self) -> Board {
+        self.board
+    }
+
+    /// Returns the expected node counts as `(depth, nodes)` pairs.
+    pub fn get_expected(&self) -> &[(u8, u64)] {
+        &self.expected
+    }
+}
+
+/// A node count of a perft suite that differs from the expected one.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PerftMismatch {
+    fen: String,
+    depth: u8,
+    expected: u64,
+    actual: u64,
+}
+
+impl PerftMismatch {
+    /// Returns the FEN of the position.
+    pub fn get_fen(&self) -> &str {
+        &self.fen
+    }
+
+    /// Returns the depth of the node count.
+    pub fn get_depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the expected node count.
+    pub fn get_expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// Returns the node count found by perft.
+    pub fn get_actual(&self) -> u64 {
+        self.actual
+    }
+}
+
+impl Display for PerftMismatch {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{} depth {}: expected {} nodes, found {}", self.fen, self.depth, self.expected, self.actual)
+    }
+}
+
+/// Represents errors happened during the parsing of a perft suite.
+#[derive(Debug, Eq, PartialEq)]
+pub struct PerftSuiteError {
+    line: usize,
+    message: String,
+}
+
+impl PerftSuiteError {
+    /// Returns the number of the line in which the error happened, starting from `1`.
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the message describing the error.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for PerftSuiteError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for PerftSuiteError {}
+
+/// Parses a perft suite. Empty lines and lines starting with `#` are skipped.
+pub fn parse_suite(input: &str) -> Result<Vec<PerftEntry>, PerftSuiteError> {
+    input.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| parse_entry(line).map_err(|message| PerftSuiteError { line: index + 1, message }))
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Result<PerftEntry, String> {
+    let epd = epd::from_epd(line).map_err(|error| error.to_string())?;
+    // Other operations like `id` are allowed, but `D0` is not a valid depth
+    let invalid_depth = epd.get_operations().iter().any(|operation| match operation {
+        Operation::Other(opcode, _) => opcode.starts_with('D') && opcode[1..].chars().all(|c| c.is_ascii_digit()),
+        _ => false,
+    });
+    if invalid_depth {
+        return Err(format!("Unable to parse node counts: {}", line));
+    }
+    let expected = epd.get_perft();
+
+    // The position is written without the operations, the clocks are kept if present
+    let fields: Vec<_> = line.split(';').next().unwrap_or("").split_whitespace().collect();
+    let clocks = fields.len() >= 6 && fields[4..6].iter().all(|field| field.starts_with(|c: char| c.is_ascii_digit()));
+    Ok(PerftEntry {
+        fen: fields[..if clocks { 6 } else { 4 }].join(" "),
+        board: epd.get_board(),
+        expected,
+    })
+}
+
+/// Runs the perft suite up to `max_depth`, and returns the node counts that differ from the expected.
+pub fn run_suite(entries: &[PerftEntry], max_depth: u8) -> Vec<PerftMismatch> {
+    let mut mismatches = Vec::new();
+
+    for entry in entries {
+        for &(depth, expected) in entry.expected.iter().filter(|(depth, _)| *depth <= max_depth) {
+            let actual = perft(&entry.board, depth);
+            if actual != expected {
+                mismatches.push(PerftMismatch {
+                    fen: entry.fen.clone(),
+                    depth,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+
+    mismatches
+}
@@ -0,0 +1,83 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::square::{File, Rank, Square};
+use crate::engine::perft;
+
+#[test]
+fn perft_initial() {
+    let board = Board::new();
+    assert_eq!(1, perft::perft(&board, 0));
+    assert_eq!(20, perft::perft(&board, 1));
+    assert_eq!(400, perft::perft(&board, 2));
+    assert_eq!(8902, perft::perft(&board, 3));
+}
+
+#[test]
+fn divide_initial() {
+    let board = Board::new();
+    let result = perft::divide(&board, 3);
+    let e4 = ChessMove::new(Square::from_pos(Rank::B, File::Fifth), Square::from_pos(Rank::D, File::Fifth));
+
+    assert_eq!(20, result.len());
+    assert_eq!(8902, result.iter().map(|(_, nodes)| nodes).sum::<u64>());
+    assert_eq!(Some(&(e4, 600)), result.iter().find(|(chess_move, _)| *chess_move == e4));
+}
+
+#[test]
+fn divide_depth_zero() {
+    assert!(perft::divide(&Board::new(), 0).is_empty());
+    assert_eq!(20, perft::divide(&Board::new(), 1).len());
+}
+
+#[test]
+fn parse_suite() {
+    let suite = perft::parse_suite("# comment\n\n8/8/8/8/8/8/8/K6k w - - ;D1 3 ;D2 9\n").unwrap();
+    assert_eq!(1, suite.len());
+    assert_eq!("8/8/8/8/8/8/8/K6k w - -", suite[0].get_fen());
+    assert_eq!(&[(1, 3), (2, 9)], suite[0].get_expected());
+}
+
+#[test]
+fn parse_suite_clocks_and_ids() {
+    let suite = perft::parse_suite("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1;D1 20
+        8/8/8/8/8/8/8/K6k w - - id \"kings\"; c0 \"bare\"; D1 3
+").unwrap();
+    assert_eq!(2, suite.len());
+    assert_eq!(&[(1, 20)], suite[0].get_expected());
+    assert_eq!("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", suite[0].get_fen());
+    assert_eq!(&[(1, 3)], suite[1].get_expected());
+    assert_eq!("8/8/8/8/8/8/8/K6k w - -", suite[1].get_fen());
+}
+
+#[test]
+fn parse_suite_error() {
+    let error = perft::parse_suite("8/8/8/8/8/8/8/K6k w - - ;D1 3\n8/8/8/8/8/8/8/K6k w - - ;D1 x").unwrap_err();
+    assert_eq!(2, error.get_line());
+
+    let error = perft::parse_suite("8/8/8/8/8/8/8/K6 w - - ;D1 3").unwrap_err();
+    assert_eq!(1, error.get_line());
+
+    let error = perft::parse_suite("8/8/8/8/8/8/8/K6k w - - ;D0 1").unwrap_err();
+    assert_eq!(1, error.get_line());
+}
+
+#[test]
+fn run_suite_mismatch() {
+    let suite = perft::parse_suite("8/8/8/8/8/8/8/K6k w - - ;D1 3 ;D2 10 ;D3 1").unwrap();
+    let mismatches = perft::run_suite(&suite, 2);
+
+    assert_eq!(1, mismatches.len());
+    assert_eq!(2, mismatches[0].get_depth());
+    assert_eq!(10, mismatches[0].get_expected());
+    assert_eq!(9, mismatches[0].get_actual());
+}
+
+#[test]
+fn run_suite_reference_positions() {
+    let suite = perft::parse_suite(include_str!("perftsuite.epd")).unwrap();
+    let mismatches = perft::run_suite(&suite, 3);
+
+    let mismatches: Vec<String> = mismatches.iter().map(|mismatch| mismatch.to_string()).collect();
+    assert!(mismatches.is_empty(), "{:?}", mismatches);
+}
+
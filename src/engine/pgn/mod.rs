@@ -0,0 +1,346 @@
+//! This module implements reading and writing games in Portable Game Notation (PGN).
+//!
+//! A PGN game consists of tag pairs like `[White "Carlsen"]`, followed by the movetext: moves in
+//! Standard Algebraic Notation with optional move numbers, comments in braces or after a
+//! semicolon, Numeric Annotation Glyphs like `$1`, recursive variations in parentheses, and the
+//! result of the game.
+//!
+//! Every move is replayed on a `Board`, so the games read are always legal. Games are written in
+//! export format, with the SAN of every move generated from the board.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::game::{Game, GameResult};
+
+pub use self::reader::PgnReader;
+pub use self::writer::PgnWriter;
+
+mod reader;
+mod writer;
+#[cfg(test)]
+mod tests;
+
+/// A move of the movetext, with its' annotations.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PgnMove {
+    chess_move: ChessMove,
+    san: String,
+    nags: Vec<u8>,
+    comment: Option<String>,
+    variations: Vec<Variation>,
+}
+
+impl PgnMove {
+    /// Creates a move without annotations.
+    pub fn new(chess_move: ChessMove, san: String) -> PgnMove {
+        PgnMove {
+            chess_move,
+            san,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+        }
+    }
+
+    /// Returns the move.
+    pub fn get_move(&self) -> ChessMove {
+        self.chess_move
+    }
+
+    /// Returns the move in SAN, as it was written in the movetext.
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns the Numeric Annotation Glyphs of the move.
+    ///
+    /// Move suffix annotations like `!` or `?!` are converted to their' NAGs.
+    pub fn get_nags(&self) -> &[u8] {
+        &self.nags
+    }
+
+    /// Returns the comment written after the move.
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Returns the variations, which are alternatives to this move, played from the same position.
+    pub fn get_variations(&self) -> &[Variation] {
+        &self.variations
+    }
+
+    /// Returns the evaluation of the `[%eval]` command in the comment.
+    pub fn get_eval(&self) -> Option<Evaluation> {
+        let eval = self.get_command("eval")?;
+        match eval.strip_prefix('#') {
+            Some(mate) => mate.parse().ok().map(Evaluation::Mate),
+            None => {
+                let pawns: f64 = eval.parse().ok()?;
+                Some(Evaluation::Centipawns((pawns * 100.0).round() as i32))
+            }
+        }
+    }
+
+    /// Returns the remaining time of the `[%clk]` command in the comment.
+    pub fn get_clock(&self) -> Option<Duration> {
+        let mut seconds = 0;
+        for part in self.get_command("clk")?.split(':') {
+            // Fractions of a second are ignored
+            let part = part.split('.').next()?;
+            seconds = seconds * 60 + part.parse::<u64>().ok()?;
+        }
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// Appends the text to the comment of the move.
+    ///
+    /// A comment cannot contain a closing brace, the braces are removed when the game is written.
+    pub fn add_comment(&mut self, comment: &str) -> &mut Self {
+        self.comment = Some(join_comments(self.comment.take(), comment.to_owned()));
+        self
+    }
+
+    /// Adds a Numeric Annotation Glyph to the move.
+    pub fn add_nag(&mut self, nag: u8) -> &mut Self {
+        self.nags.push(nag);
+        self
+    }
+
+    /// Adds a variation, which has to start from the same position as this move.
+    ///
+    /// The game cannot be written if the moves of the variation are illegal in that position,
+    /// `PgnWriter::write_game()` returns an error.
+    pub fn add_variation(&mut self, variation: Variation) -> &mut Self {
+        self.variations.push(variation);
+        self
+    }
+
+    /// Sets the `[%eval]` command of the comment, replacing the previous evaluation.
+    pub fn set_eval(&mut self, eval: Evaluation) -> &mut Self {
+        let value = match eval {
+            Evaluation::Centipawns(score) => {
+                let sign = if score < 0 { "-" } else { "" };
+                format!("{}{}.{:02}", sign, score.abs() / 100, score.abs() % 100)
+            }
+            Evaluation::Mate(moves) => format!("#{}", moves),
+        };
+        self.set_command("eval", &value)
+    }
+
+    /// Sets the `[%clk]` command of the comment, replacing the previous clock time.
+    pub fn set_clock(&mut self, remaining: Duration) -> &mut Self {
+        let seconds = remaining.as_secs();
+        let value = format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+        self.set_command("clk", &value)
+    }
+
+    /// Returns the value of an embedded command like `[%clk 0:05:00]` in the comment.
+    fn get_command(&self, name: &str) -> Option<&str> {
+        let comment = self.comment.as_deref()?;
+        let (start, end) = find_command(comment, name)?;
+        comment[start..end].trim_start_matches('[').trim_end_matches(']')
+            .split_whitespace()
+            .nth(1)
+    }
+
+    fn set_command(&mut self, name: &str, value: &str) -> &mut Self {
+        let command = format!("[%{} {}]", name, value);
+        let comment = match self.comment.take() {
+            Some(comment) => match find_command(&comment, name) {
+                Some((start, end)) => format!("{}{}{}", &comment[..start], command, &comment[end..]),
+                None => join_comments(Some(comment), command),
+            },
+            None => command,
+        };
+        self.comment = Some(comment);
+        self
+    }
+}
+
+/// An engine evaluation, as stored in the `[%eval]` command of a comment.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Evaluation {
+    /// The score in centipawns, from White's point of view.
+    Centipawns(i32),
+    /// Mate in the specified number of moves, negative if Black is mating.
+    Mate(i32),
+}
+
+/// A sequence of moves, either the main line of a game or a variation.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Variation {
+    comment: Option<String>,
+    moves: Vec<PgnMove>,
+}
+
+impl Variation {
+    /// Creates a variation from moves played on the board.
+    ///
+    /// Returns `None` if any of the moves are illegal.
+    pub fn from_moves(board: &Board, moves: &[ChessMove]) -> Option<Variation> {
+        let mut board = *board;
+        let mut variation = Variation::default();
+        for &chess_move in moves {
+            let next = board.make_move(chess_move)?;
+            variation.moves.push(PgnMove::new(chess_move, board.to_san(chess_move)));
+            board = next;
+        }
+        Some(variation)
+    }
+
+    /// Returns the comment written before the first move.
+    pub fn get_comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Returns the moves of the variation.
+    pub fn get_moves(&self) -> &[PgnMove] {
+        &self.moves
+    }
+
+    /// Returns the moves of the variation, to add annotations to them.
+    pub fn get_moves_mut(&mut self) -> &mut [PgnMove] {
+        &mut self.moves
+    }
+
+    /// Appends the text to the comment written before the first move.
+    ///
+    /// A comment cannot contain a closing brace, the braces are removed when the game is written.
+    pub fn add_comment(&mut self, comment: &str) -> &mut Self {
+        self.comment = Some(join_comments(self.comment.take(), comment.to_owned()));
+        self
+    }
+}
+
+/// A game read from or written to PGN.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PgnGame {
+    tags: Vec<(String, String)>,
+    mainline: Variation,
+    result: GameResult,
+    game: Game,
+}
+
+impl PgnGame {
+    /// Creates a game without tags from the move history of the `game`.
+    pub fn from_game(game: &Game, result: GameResult) -> PgnGame {
+        PgnGame {
+            tags: Vec::new(),
+            mainline: Variation::from_moves(&game.get_initial_board(), game.get_moves()).unwrap(),
+            result,
+            game: game.clone(),
+        }
+    }
+
+    /// Returns the tag pairs, in the order they were written.
+    pub fn get_tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    /// Returns the value of the tag with the specified name.
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of the tag, replacing the previous value.
+    pub fn set_tag(&mut self, name: &str, value: &str) -> &mut Self {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some(tag) => tag.1 = value.to_owned(),
+            None => self.tags.push((name.to_owned(), value.to_owned())),
+        }
+        self
+    }
+
+    /// Returns the main line of the game, with the annotations and variations.
+    pub fn get_mainline(&self) -> &Variation {
+        &self.mainline
+    }
+
+    /// Returns the main line of the game, to add annotations and variations to it.
+    pub fn get_mainline_mut(&mut self) -> &mut Variation {
+        &mut self.mainline
+    }
+
+    /// Returns the result written at the end of the movetext.
+    pub fn get_result(&self) -> GameResult {
+        self.result
+    }
+
+    /// Sets the result of the game.
+    pub fn set_result(&mut self, result: GameResult) -> &mut Self {
+        self.result = result;
+        self
+    }
+
+    /// Returns the game replayed from the main line.
+    pub fn get_game(&self) -> &Game {
+        &self.game
+    }
+}
+
+/// Represents an error which happened while reading PGN.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PgnError {
+    line: usize,
+    column: usize,
+    message: String,
+    san: Option<String>,
+    io: bool,
+}
+
+impl PgnError {
+    /// Returns the number of the line in which the error happened, starting from `1`.
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column in which the error happened, starting from `1`.
+    pub fn get_column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the message describing the error.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the move which could not be read, if the error was caused by a move.
+    pub fn get_san(&self) -> Option<&str> {
+        self.san.as_deref()
+    }
+
+    /// Checks whether the error was caused by the input, which could not be read, and not by the
+    /// content of a game. No more games can be read after such an error.
+    pub fn is_io_error(&self) -> bool {
+        self.io
+    }
+}
+
+impl Display for PgnError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "Line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for PgnError {}
+
+fn join_comments(first: Option<String>, second: String) -> String {
+    match first {
+        Some(first) => format!("{} {}", first, second),
+        None => second,
+    }
+}
+
+/// Returns the byte range of the embedded command with the specified name in the comment.
+fn find_command(comment: &str, name: &str) -> Option<(usize, usize)> {
+    let start = comment.find(&format!("[%{} ", name))?;
+    let end = start + comment[start..].find(']')? + 1;
+    Some((start, end))
+}
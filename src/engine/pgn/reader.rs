@@ -0,0 +1,425 @@
+//! This module implements a streaming PGN reader.
+//!
+//! The input is read line by line, and the games are parsed one by one when the reader is iterated,
+//! so arbitrarily large game collections can be processed in bounded memory.
+
+use std::io::BufRead;
+
+use crate::engine::board::Board;
+use crate::engine::fen;
+use crate::engine::game::{Game, GameResult};
+use crate::engine::pgn::{PgnError, PgnGame, PgnMove, Variation};
+
+/// The tokens of PGN.
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum TokenKind {
+    /// A tag pair: the name and the value of the tag.
+    Tag(String, String),
+    /// A move, a move number or a result.
+    Symbol(String),
+    Comment(String),
+    Nag(u8),
+    /// The start of a variation.
+    Open,
+    /// The end of a variation.
+    Close,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+/// Reads PGN games from a buffered reader.
+///
+/// The reader is an iterator of games. If a game cannot be read, the error is returned in place of
+/// the game, and the reader continues with the next game.
+///
+/// # Example
+///
+/// ```ignore
+/// let reader = PgnReader::new(BufReader::new(File::open("games.pgn")?));
+/// for game in reader {
+///     println!("{:?}", game?.get_tag("White"));
+/// }
+/// ```
+pub struct PgnReader<R> {
+    reader: R,
+    /// The characters of the current line.
+    chars: Vec<char>,
+    /// The position of the next character in the current line.
+    position: usize,
+    /// The number of the current line, starting from `1`.
+    line: usize,
+    eof: bool,
+    peeked: Option<Token>,
+    in_movetext: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    /// Creates a new reader.
+    pub fn new(reader: R) -> PgnReader<R> {
+        PgnReader {
+            reader,
+            chars: Vec::new(),
+            position: 0,
+            line: 0,
+            eof: false,
+            peeked: None,
+            in_movetext: false,
+        }
+    }
+
+    /// Reads the next game, or returns `None` if there are no more games.
+    ///
+    /// If the game cannot be read, the rest of the game is skipped, so the next call reads the
+    /// next game.
+    pub fn read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        let result = self.parse_game();
+        if result.is_err() {
+            self.skip_game();
+        }
+        result
+    }
+
+    fn parse_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        self.in_movetext = false;
+        let mut tags = Vec::new();
+        let mut board = Board::new();
+
+        loop {
+            match self.next_token()? {
+                Some(Token { kind: TokenKind::Tag(name, value), line, column }) => {
+                    if name == "FEN" {
+                        board = fen::from_fen(&value)
+                            .map_err(|error| error_at(line, column, format!("Invalid FEN tag: {}", error), None))?;
+                    }
+                    tags.push((name, value));
+                }
+                Some(token) => {
+                    self.peeked = Some(token);
+                    break;
+                }
+                None if tags.is_empty() => return Ok(None),
+                None => break,
+            }
+        }
+
+        self.in_movetext = true;
+        let mut game = Game::from_board(board);
+        let (mainline, result) = self.parse_variation(board, Some(&mut game))?;
+        Ok(Some(PgnGame {
+            tags,
+            mainline,
+            result: result.unwrap_or(GameResult::Unknown),
+            game,
+        }))
+    }
+
+    /// Parses the moves of a variation starting from the position, until the end of the variation.
+    ///
+    /// The main line is parsed with the `game`, and ends with the result, the next game's tags or
+    /// the end of the input. Other variations end with a closing parenthesis.
+    fn parse_variation(&mut self, mut board: Board, mut game: Option<&mut Game>) -> Result<(Variation, Option<GameResult>), PgnError> {
+        let mainline = game.is_some();
+        let mut variation = Variation::default();
+        // The position before the last move, variations of the last move start from here
+        let mut previous = board;
+
+        loop {
+            let token = match self.next_token()? {
+                Some(token) => token,
+                None if mainline => return Ok((variation, None)),
+                None => return Err(self.error_here("Unterminated variation")),
+            };
+
+            let (line, column) = (token.line, token.column);
+            match token.kind {
+                TokenKind::Tag(_, _) if mainline => {
+                    self.peeked = Some(token);
+                    return Ok((variation, None));
+                }
+                TokenKind::Tag(_, _) => {
+                    return Err(error_at(line, column, "Unexpected tag in a variation".to_owned(), None));
+                }
+                TokenKind::Symbol(symbol) => {
+                    if let Some(result) = GameResult::from_string(&symbol) {
+                        if mainline {
+                            return Ok((variation, Some(result)));
+                        }
+                        continue;
+                    }
+
+                    let san = strip_move_number(&symbol);
+                    if san.is_empty() {
+                        continue;
+                    }
+
+                    let (san, nag) = split_suffix_annotation(san);
+                    let chess_move = board.parse_san(san)
+                        .map_err(|error| error_at(line, column, error.to_string(), Some(san.to_owned())))?;
+
+                    let mut pgn_move = PgnMove::new(chess_move, san.to_owned());
+                    pgn_move.nags.extend(nag);
+                    variation.moves.push(pgn_move);
+
+                    previous = board;
+                    board = board.make_move(chess_move).unwrap();
+                    if let Some(game) = game.as_deref_mut() {
+                        game.push(chess_move);
+                    }
+                }
+                TokenKind::Comment(comment) => match variation.moves.last_mut() {
+                    Some(last) => {
+                        last.add_comment(&comment);
+                    }
+                    None => {
+                        variation.add_comment(&comment);
+                    }
+                },
+                TokenKind::Nag(nag) => match variation.moves.last_mut() {
+                    Some(last) => last.nags.push(nag),
+                    None => return Err(error_at(line, column, "Annotation before the first move".to_owned(), None)),
+                },
+                TokenKind::Open => {
+                    if variation.moves.is_empty() {
+                        return Err(error_at(line, column, "Variation before the first move".to_owned(), None));
+                    }
+                    let (alternative, _) = self.parse_variation(previous, None)?;
+                    variation.moves.last_mut().unwrap().variations.push(alternative);
+                }
+                TokenKind::Close if mainline => {
+                    return Err(error_at(line, column, "Unexpected end of variation".to_owned(), None));
+                }
+                TokenKind::Close => return Ok((variation, None)),
+            }
+        }
+    }
+
+    /// Skips the rest of the current game, until its' result or the tags of the next game.
+    fn skip_game(&mut self) {
+        loop {
+            match self.next_token() {
+                Ok(None) => return,
+                Ok(Some(Token { kind: TokenKind::Symbol(symbol), .. })) if GameResult::from_string(&symbol).is_some() => return,
+                Ok(Some(token)) => {
+                    if let TokenKind::Tag(_, _) = token.kind {
+                        if self.in_movetext {
+                            self.peeked = Some(token);
+                            return;
+                        }
+                    } else {
+                        self.in_movetext = true;
+                    }
+                }
+                Err(_) => {
+                    if self.eof {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    //
+    // Lexer
+    //
+
+    fn next_token(&mut self) -> Result<Option<Token>, PgnError> {
+        if let Some(token) = self.peeked.take() {
+            return Ok(Some(token));
+        }
+
+        while let Some(c) = self.peek_char()? {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.position += 1;
+        }
+
+        let (line, column) = (self.line, self.position + 1);
+        let kind = match self.next_char()? {
+            None => return Ok(None),
+            Some('[') => self.read_tag(line, column)?,
+            Some('{') => {
+                let mut comment = String::new();
+                loop {
+                    match self.next_char()? {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => return Err(error_at(line, column, "Unterminated comment".to_owned(), None)),
+                    }
+                }
+                TokenKind::Comment(normalize_whitespace(&comment))
+            }
+            Some(';') => {
+                let comment: String = self.chars[self.position..].iter().collect();
+                self.position = self.chars.len();
+                TokenKind::Comment(normalize_whitespace(&comment))
+            }
+            Some('$') => {
+                let digits = self.read_while(|c| c.is_ascii_digit())?;
+                let nag = digits.parse()
+                    .map_err(|_| error_at(line, column, format!("Invalid annotation: ${}", digits), None))?;
+                TokenKind::Nag(nag)
+            }
+            Some('(') => TokenKind::Open,
+            Some(')') => TokenKind::Close,
+            Some(c) if is_symbol_char(c) => {
+                let mut symbol = c.to_string();
+                symbol.push_str(&self.read_while(is_symbol_char)?);
+                TokenKind::Symbol(symbol)
+            }
+            Some(c) => return Err(error_at(line, column, format!("Unexpected character: {}", c), None)),
+        };
+
+        Ok(Some(Token { kind, line, column }))
+    }
+
+    /// Reads a tag pair, after the opening bracket. Errors are reported at the opening bracket.
+    fn read_tag(&mut self, line: usize, column: usize) -> Result<TokenKind, PgnError> {
+        self.read_while(char::is_whitespace)?;
+        let name = self.read_while(|c| c.is_alphanumeric() || c == '_')?;
+        self.read_while(char::is_whitespace)?;
+        if name.is_empty() || self.next_char()? != Some('"') {
+            return Err(error_at(line, column, "Invalid tag".to_owned(), None));
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.next_char()? {
+                Some('"') => break,
+                Some('\\') => match self.next_char()? {
+                    Some(c @ '"') | Some(c @ '\\') => value.push(c),
+                    _ => return Err(error_at(line, column, "Invalid escape in tag value".to_owned(), None)),
+                },
+                Some('\n') | None => return Err(error_at(line, column, "Unterminated tag value".to_owned(), None)),
+                Some(c) => value.push(c),
+            }
+        }
+
+        self.read_while(char::is_whitespace)?;
+        if self.next_char()? != Some(']') {
+            return Err(error_at(line, column, "Invalid tag".to_owned(), None));
+        }
+        Ok(TokenKind::Tag(name, value))
+    }
+
+    /// Reads the characters while they match the predicate.
+    fn read_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> Result<String, PgnError> {
+        let mut result = String::new();
+        while let Some(c) = self.peek_char()? {
+            if !predicate(c) {
+                break;
+            }
+            result.push(c);
+            self.position += 1;
+        }
+        Ok(result)
+    }
+
+    fn peek_char(&mut self) -> Result<Option<char>, PgnError> {
+        if self.fill()? {
+            Ok(Some(self.chars[self.position]))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_char(&mut self) -> Result<Option<char>, PgnError> {
+        let c = self.peek_char()?;
+        if c.is_some() {
+            self.position += 1;
+        }
+        Ok(c)
+    }
+
+    /// Makes sure there is a character to read, reading the next line if needed.
+    ///
+    /// Returns `false` at the end of the input. Lines starting with `%` are skipped.
+    fn fill(&mut self) -> Result<bool, PgnError> {
+        while self.position >= self.chars.len() {
+            if self.eof {
+                return Ok(false);
+            }
+
+            let mut buffer = String::new();
+            match self.reader.read_line(&mut buffer) {
+                Ok(0) => self.eof = true,
+                Ok(_) => {
+                    self.line += 1;
+                    self.position = 0;
+                    self.chars = if buffer.starts_with('%') {
+                        vec!['\n']
+                    } else {
+                        buffer.chars().collect()
+                    };
+                }
+                Err(error) => {
+                    self.eof = true;
+                    return Err(PgnError {
+                        io: true,
+                        ..error_at(self.line + 1, 1, format!("Unable to read the input: {}", error), None)
+                    });
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    fn error_here(&self, message: &str) -> PgnError {
+        error_at(self.line, self.position + 1, message.to_owned(), None)
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_game().transpose()
+    }
+}
+
+fn error_at(line: usize, column: usize, message: String, san: Option<String>) -> PgnError {
+    PgnError {
+        line,
+        column,
+        message,
+        san,
+        io: false,
+    }
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "+#=:-/_.!?*".contains(c)
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Removes the move number written before the move, like `12.` or `12...`.
+fn strip_move_number(symbol: &str) -> &str {
+    if symbol.starts_with("0-0") {
+        symbol
+    } else {
+        symbol.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start_matches('.')
+    }
+}
+
+/// Splits the move suffix annotation (like `!` or `?!`) from the move, and converts it to a NAG.
+fn split_suffix_annotation(san: &str) -> (&str, Option<u8>) {
+    let stripped = san.trim_end_matches(['!', '?']);
+    let nag = match &san[stripped.len()..] {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    };
+    (stripped, nag)
+}
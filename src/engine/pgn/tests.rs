@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+use crate::engine::board::Board;
+use crate::engine::fen;
+use crate::engine::game::{Game, GameResult};
+use crate::engine::pgn::{Evaluation, PgnGame, PgnReader, PgnWriter, Variation};
+
+const GAMES: &str = r#"[Event "F/S Return Match"]
+[Site "Belgrade, Serbia JUG"]
+[Date "1992.11.04"]
+[Round "29"]
+[White "Fischer, Robert J."]
+[Black "Spassky, Boris V."]
+[Result "1/2-1/2"]
+
+{Opening comment} 1. e4 e5 2. Nf3 Nc6 3. Bb5 {This opening is called the Ruy Lopez.}
+3... a6 4. Ba4 Nf6 5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 10. d4 Nbd7
+11. c4 c6 12. cxb5 axb5 13. Nc3 Bb7 14. Bg5 b4 15. Nb1 h6 16. Bh4 c5 17. dxe5
+Nxe4 18. Bxe7 Qxe7 19. exd6 Qf6 20. Nbd2 Nxd6 21. Nc4 Nxc4 22. Bxc4 Nb6
+23. Ne5 Rae8 24. Bxf7+ Rxf7 25. Nxf7 Rxe1+ 26. Qxe1 Kxf7 27. Qe3 Qg5 28. Qxg5
+hxg5 29. b3 Ke6 30. a3 Kd6 31. axb4 cxb4 32. Ra5 Nd5 33. f3 Bc8 34. Kf2 Bf5
+35. Ra7 g6 36. Ra6+ Kc5 37. Ke1 Nf4 38. g3 Nxh3 39. Kd2 Kb5 40. Rd6 Kc5 41. Ra6
+Nf2 42. g4 Bd3 43. Re6 1/2-1/2
+
+[Event "Annotated"]
+[Result "1-0"]
+
+1. e4! e5 2. Qh5?! Nc6 $2 (2... g6 3. Qxe5+ (3. Qf3) 3... Qe7) 3. Bc4 Nf6?? ; blunder
+4. Qxf7# 1-0
+"#;
+
+fn write(game: &PgnGame) -> String {
+    let mut writer = PgnWriter::new(Vec::new());
+    writer.write_game(game).unwrap();
+    String::from_utf8(writer.into_inner()).unwrap()
+}
+
+#[test]
+fn read_games() {
+    let games: Vec<_> = PgnReader::new(GAMES.as_bytes()).collect();
+    assert_eq!(2, games.len());
+
+    let game = games[0].as_ref().unwrap();
+    assert_eq!(7, game.get_tags().len());
+    assert_eq!(Some("Fischer, Robert J."), game.get_tag("White"));
+    assert_eq!(None, game.get_tag("ECO"));
+    assert_eq!(GameResult::Draw, game.get_result());
+    assert_eq!(85, game.get_game().get_ply());
+    assert_eq!(Some("Opening comment"), game.get_mainline().get_comment());
+    assert_eq!(Some("This opening is called the Ruy Lopez."), game.get_mainline().get_moves()[4].get_comment());
+    assert_eq!("O-O", game.get_mainline().get_moves()[8].get_san());
+}
+
+#[test]
+fn annotations_and_variations() {
+    let game = PgnReader::new(GAMES.as_bytes()).nth(1).unwrap().unwrap();
+    let moves = game.get_mainline().get_moves();
+
+    assert_eq!(GameResult::WhiteWins, game.get_result());
+    assert_eq!(7, moves.len());
+    assert_eq!(&[1], moves[0].get_nags());
+    assert_eq!("e4", moves[0].get_san());
+    assert_eq!(&[6], moves[2].get_nags());
+    assert_eq!(&[2], moves[3].get_nags());
+    assert_eq!(&[4], moves[5].get_nags());
+    assert_eq!(Some("blunder"), moves[5].get_comment());
+    assert!(game.get_game().get_board().status().is_game_over());
+
+    // 2... g6 3. Qxe5+ (3. Qf3) 3... Qe7 is an alternative to 2... Nc6
+    let variations = moves[3].get_variations();
+    assert_eq!(1, variations.len());
+    let variation = variations[0].get_moves();
+    assert_eq!(vec!["g6", "Qxe5+", "Qe7"], variation.iter().map(|m| m.get_san()).collect::<Vec<_>>());
+    assert_eq!("Qf3", variation[1].get_variations()[0].get_moves()[0].get_san());
+}
+
+#[test]
+fn fen_tag() {
+    let pgn = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/4K2R w K - 0 1\"]\n\n1. O-O Kd7 *\n";
+    let game = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(GameResult::Unknown, game.get_result());
+    assert_eq!("4k3/8/8/8/8/8/8/4K2R w K - 0 1", game.get_game().get_initial_board().to_fen());
+    assert_eq!("8/3k4/8/8/8/8/8/5RK1 w - - 2 2", game.get_game().get_board().to_fen());
+}
+
+#[test]
+fn illegal_move_error() {
+    let pgn = "[Event \"Broken\"]\n\n1. e4 e5\n2. Ke3 Nc6 1-0\n\n[Event \"Next\"]\n\n1. d4 *\n";
+    let mut reader = PgnReader::new(pgn.as_bytes());
+
+    let error = reader.next().unwrap().unwrap_err();
+    assert_eq!(4, error.get_line());
+    assert_eq!(4, error.get_column());
+    assert_eq!(Some("Ke3"), error.get_san());
+    assert_eq!("Line 4, column 4: Illegal move: Ke3", error.to_string());
+
+    // The reader continues with the next game
+    let next = reader.next().unwrap().unwrap();
+    assert_eq!(Some("Next"), next.get_tag("Event"));
+    assert_eq!(1, next.get_game().get_ply());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn syntax_errors() {
+    let errors = [
+        ("[Event \"Unterminated]\n1. e4 *", 1, 1),
+        ("1. e4 {comment *", 1, 7),
+        ("1. e4 e5 (1... c5 *", 1, 20),
+        ("1. e4 ) *", 1, 7),
+        ("( 1. e4 ) *", 1, 1),
+        ("1. e4 & *", 1, 7),
+        ("[FEN \"8/8\"]\n1. e4 *", 1, 1),
+    ];
+    for (pgn, line, column) in errors.iter() {
+        let error = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap_err();
+        assert_eq!((*line, *column), (error.get_line(), error.get_column()), "{}: {}", pgn, error);
+    }
+}
+
+#[test]
+fn escapes_and_comments() {
+    let pgn = "[Event \"The \\\"Big\\\" one\"]\n% escaped line with ( and {\n1. e4 ; rest of line\n{multi\nline} e5 $14 *";
+    let game = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+    let moves = game.get_mainline().get_moves();
+
+    assert_eq!(Some("The \"Big\" one"), game.get_tag("Event"));
+    assert_eq!(Some("rest of line multi line"), moves[0].get_comment());
+    assert_eq!(&[14], moves[1].get_nags());
+}
+
+#[test]
+fn empty_input() {
+    assert!(PgnReader::new("".as_bytes()).next().is_none());
+    assert!(PgnReader::new("\n\n  \n".as_bytes()).next().is_none());
+}
+
+#[test]
+fn game_without_result() {
+    let pgn = "1. e4 e5\n\n[Event \"Second\"]\n1. d4 d5 1/2-1/2";
+    let games: Vec<_> = PgnReader::new(pgn.as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(2, games.len());
+    assert_eq!(GameResult::Unknown, games[0].get_result());
+    assert_eq!(Board::new(), games[0].get_game().get_initial_board());
+    assert_eq!(2, games[0].get_game().get_ply());
+    assert_eq!(GameResult::Draw, games[1].get_result());
+}
+
+#[test]
+fn write_annotated_game() {
+    let pgn = "[Event \"Annotated\"]\n[Result \"1-0\"]\n[Annotator \"Me\"]\n\n\
+        {Start} 1. e4! e5 2. Qh5?! Nc6 $2 (2... g6 3. Qxe5+ (3. Qf3) 3... Qe7) 3. Bc4 Nf6?? {blunder} 4. Qxf7# 1-0\n";
+    let game = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+
+    let expected = "[Event \"Annotated\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"?\"]\n\
+        [Black \"?\"]\n[Result \"1-0\"]\n[Annotator \"Me\"]\n\n\
+        {Start} 1. e4 $1 e5 2. Qh5 $6 Nc6 $2 (2... g6 3. Qxe5+ (3. Qf3) 3... Qe7) 3. Bc4\n\
+        Nf6 $4 {blunder} 4. Qxf7# 1-0\n";
+    assert_eq!(expected, write(&game));
+
+    let read = PgnReader::new(expected.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(game.get_mainline(), read.get_mainline());
+    assert_eq!(Some("?"), read.get_tag("Site"));
+}
+
+#[test]
+fn write_wraps_lines() {
+    let games: Vec<_> = PgnReader::new(GAMES.as_bytes()).map(Result::unwrap).collect();
+
+    let mut writer = PgnWriter::new(Vec::new());
+    for game in &games {
+        writer.write_game(game).unwrap();
+    }
+    let pgn = String::from_utf8(writer.into_inner()).unwrap();
+
+    assert!(pgn.lines().all(|line| line.len() <= 80));
+    assert!(pgn.contains("\n\n[Event \"Annotated\"]\n"));
+    let read: Vec<_> = PgnReader::new(pgn.as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(games.iter().map(|game| game.get_mainline()).collect::<Vec<_>>(),
+               read.iter().map(|game| game.get_mainline()).collect::<Vec<_>>());
+}
+
+#[test]
+fn write_from_game() {
+    let board = fen::from_fen("4k3/8/8/8/8/8/4P3/4K2R b K - 3 20").unwrap();
+    let mut game = Game::from_board(board);
+    for san in &["Kd7", "O-O", "Ke6", "e4"] {
+        let chess_move = game.get_board().parse_san(san).unwrap();
+        game.push(chess_move);
+    }
+
+    let mut pgn = PgnGame::from_game(&game, GameResult::Unknown);
+    pgn.set_tag("White", "Engine").set_tag("FEN", "ignored");
+    {
+        let moves = pgn.get_mainline_mut().get_moves_mut();
+        moves[0].set_eval(Evaluation::Centipawns(-35)).set_clock(Duration::from_secs(3725));
+        moves[1].add_comment("castles").set_eval(Evaluation::Mate(-3)).set_eval(Evaluation::Centipawns(120));
+    }
+
+    let moves = pgn.get_mainline().get_moves();
+    assert_eq!(Some(Evaluation::Centipawns(-35)), moves[0].get_eval());
+    assert_eq!(Some(Duration::from_secs(3725)), moves[0].get_clock());
+    assert_eq!(Some("castles [%eval 1.20]"), moves[1].get_comment());
+    assert_eq!(None, moves[2].get_eval());
+
+    let expected = "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"Engine\"]\n\
+        [Black \"?\"]\n[Result \"*\"]\n[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K2R b K - 3 20\"]\n\n\
+        20... Kd7 {[%eval -0.35] [%clk 1:02:05]} 21. O-O {castles [%eval 1.20]}\n\
+        21... Ke6 22. e4 *\n";
+    assert_eq!(expected, write(&pgn));
+}
+
+#[test]
+fn write_strips_closing_braces() {
+    let mut pgn = PgnGame::from_game(&Game::new(), GameResult::Unknown);
+    pgn.get_mainline_mut().add_comment("no {nested} braces");
+
+    let read = PgnReader::new(write(&pgn).as_bytes()).next().unwrap().unwrap();
+    assert_eq!(Some("no {nested braces"), read.get_mainline().get_comment());
+}
+
+#[test]
+fn write_illegal_variation() {
+    let board = Board::new();
+    let e2e4 = board.parse_san("e4").unwrap();
+    let mut game = Game::new();
+    game.push(e2e4);
+
+    // The variation starts from the position after 1. e4, not from the position before it
+    let after_e4 = board.make_move(e2e4).unwrap();
+    let variation = Variation::from_moves(&after_e4, &[after_e4.parse_san("e5").unwrap()]).unwrap();
+    let mut pgn = PgnGame::from_game(&game, GameResult::Unknown);
+    pgn.get_mainline_mut().get_moves_mut()[0].add_variation(variation);
+
+    let mut writer = PgnWriter::new(Vec::new());
+    assert!(writer.write_game(&pgn).is_err());
+    assert!(writer.into_inner().is_empty());
+}
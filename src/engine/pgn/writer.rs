@@ -0,0 +1,207 @@
+//! This module implements writing games in the export format of PGN.
+//!
+//! The tags start with the Seven Tag Roster, followed by the other tags of the game. The movetext
+//! is wrapped at 80 columns, and the SAN of every move is generated from the board.
+
+use std::fmt;
+use std::io;
+use std::io::Write;
+
+use crate::engine::board::Board;
+use crate::engine::board::piece::color::Color;
+use crate::engine::fen;
+use crate::engine::pgn::{PgnGame, Variation};
+
+/// The maximum length of the movetext lines.
+pub const LINE_WIDTH: usize = 80;
+
+/// The tags which are always written, in this order, with their default values.
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+/// Writes PGN games to a writer, separated by empty lines.
+pub struct PgnWriter<W> {
+    writer: W,
+    games: usize,
+}
+
+impl<W: Write> PgnWriter<W> {
+    /// Creates a new writer.
+    pub fn new(writer: W) -> PgnWriter<W> {
+        PgnWriter { writer, games: 0 }
+    }
+
+    /// Writes the game.
+    ///
+    /// Returns an error of kind `InvalidData` if a move of a variation is illegal, see
+    /// `PgnMove::add_variation()`. Nothing is written in that case.
+    pub fn write_game(&mut self, game: &PgnGame) -> io::Result<()> {
+        let mut text = String::new();
+        write_pgn(game, &mut text)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Illegal move in a variation"))?;
+
+        if self.games > 0 {
+            writeln!(self.writer)?;
+        }
+        self.games += 1;
+        self.writer.write_all(text.as_bytes())
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Writes the game in export format, the tags and the movetext followed by a newline.
+fn write_pgn<W: fmt::Write>(game: &PgnGame, w: &mut W) -> Result<(), fmt::Error> {
+    let mut movetext = Movetext::default();
+    movetext.write_variation(game.game.get_initial_board(), &game.mainline)?;
+    movetext.push(&game.result.to_string());
+
+    for (name, value) in tags(game) {
+        writeln!(w, "[{} \"{}\"]", name, escape(&value))?;
+    }
+    writeln!(w)?;
+    writeln!(w, "{}", movetext.text)
+}
+
+/// Returns the tags to write: the Seven Tag Roster, the other tags of the game, and the tags of
+/// the initial position if the game does not start from the standard position.
+fn tags(game: &PgnGame) -> Vec<(String, String)> {
+    let mut tags: Vec<_> = SEVEN_TAG_ROSTER.iter()
+        .map(|&(name, default)| {
+            let value = match name {
+                "Result" => game.result.to_string(),
+                _ => game.get_tag(name).unwrap_or(default).to_owned(),
+            };
+            (name.to_owned(), value)
+        })
+        .collect();
+
+    let board = game.game.get_initial_board();
+    let setup = board.to_fen() != fen::INITIAL_BOARD;
+    tags.extend(game.tags.iter()
+        .filter(|(name, _)| !SEVEN_TAG_ROSTER.iter().any(|(roster, _)| roster == name))
+        .filter(|(name, _)| !setup || (name != "SetUp" && name != "FEN"))
+        .cloned());
+    if setup {
+        tags.push(("SetUp".to_owned(), "1".to_owned()));
+        tags.push(("FEN".to_owned(), board.to_fen()));
+    }
+    tags
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The movetext being written, wrapped into lines.
+#[derive(Default)]
+struct Movetext {
+    text: String,
+    /// The length of the last line.
+    line_length: usize,
+    /// A variation was opened, the parenthesis is written before the next token.
+    open: bool,
+}
+
+impl Movetext {
+    /// Writes the moves of the variation played from the board, with their annotations.
+    ///
+    /// Returns an error if a move of the variation is illegal, so it has no SAN.
+    fn write_variation(&mut self, mut board: Board, variation: &Variation) -> Result<(), fmt::Error> {
+        // Black's moves are numbered at the start of a variation, and after comments and variations
+        let mut numbered = false;
+        if let Some(comment) = variation.get_comment() {
+            self.push_comment(comment);
+        }
+
+        for pgn_move in variation.get_moves() {
+            let next = board.make_move(pgn_move.get_move()).ok_or(fmt::Error)?;
+            // The move number is kept on the same line as the move
+            let san = board.to_san(pgn_move.get_move());
+            match board.get_turn() {
+                Color::White => self.push(&format!("{}. {}", board.get_full_moves(), san)),
+                Color::Black if !numbered => self.push(&format!("{}... {}", board.get_full_moves(), san)),
+                Color::Black => self.push(&san),
+            }
+            numbered = true;
+
+            for nag in pgn_move.get_nags() {
+                self.push(&format!("${}", nag));
+            }
+            if let Some(comment) = pgn_move.get_comment() {
+                self.push_comment(comment);
+                numbered = false;
+            }
+            for alternative in pgn_move.get_variations() {
+                self.open = true;
+                self.write_variation(board, alternative)?;
+                self.close();
+                numbered = false;
+            }
+
+            board = next;
+        }
+        Ok(())
+    }
+
+    /// Writes the comment word by word, so it can be wrapped.
+    fn push_comment(&mut self, comment: &str) {
+        // A closing brace would end the comment early
+        let comment = comment.replace('}', "");
+        let words: Vec<_> = comment.split_whitespace().collect();
+        match words.len() {
+            0 => self.push("{}"),
+            1 => self.push(&format!("{{{}}}", words[0])),
+            n => {
+                self.push(&format!("{{{}", words[0]));
+                for word in &words[1..n - 1] {
+                    self.push(word);
+                }
+                self.push(&format!("{}}}", words[n - 1]));
+            }
+        }
+    }
+
+    /// Writes the token, starting a new line if it does not fit the current one.
+    fn push(&mut self, token: &str) {
+        let prefix = if self.open { "(" } else { "" };
+        self.open = false;
+
+        let length = prefix.len() + token.len();
+        if self.line_length > 0 && self.line_length + 1 + length > LINE_WIDTH {
+            self.text.push('\n');
+            self.line_length = 0;
+        } else if self.line_length > 0 {
+            self.text.push(' ');
+            self.line_length += 1;
+        }
+        self.text.push_str(prefix);
+        self.text.push_str(token);
+        self.line_length += length;
+    }
+
+    /// Writes the end of a variation, right after the last token.
+    fn close(&mut self) {
+        if self.open {
+            // The variation was empty
+            self.open = false;
+            self.push("()");
+        } else if self.line_length < LINE_WIDTH {
+            self.text.push(')');
+            self.line_length += 1;
+        } else {
+            self.text.push_str("\n)");
+            self.line_length = 1;
+        }
+    }
+}
@@ -0,0 +1,575 @@
+//! This module implements the search for the best move of a position.
+//!
+//! The search is a negamax alpha-beta search: every position is scored from the point of view of
+//! the player who have to move, and the branches which cannot change the result are cut off. The
+//! search is repeated with increasing depth (iterative deepening), and the search stops when any of
+//! its' limits is reached, the last completed iteration is returned.
+//!
+//! The results of the searched positions are stored in a transposition table, which is kept between
+//! the iterations and the searches: it cuts off positions which are reached again, and its' best
+//! moves are searched first, so the next iteration gets more cutoffs. The other moves are ordered
+//! by the heuristics of the `ordering` module.
+//!
+//! At the end of the main search a quiescence search follows the captures and queen promotions,
+//! until the position is quiet, so a capture just behind the horizon is not missed. The captures
+//! which lose material by the static exchange evaluation are skipped.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut limits = SearchLimits::new();
+//! limits.set_depth(6).set_movetime(Duration::from_secs(1));
+//! let result = search::search(&Board::new(), &limits);
+//! println!("{} {}", result.get_best_move().unwrap(), result.get_score());
+//! ```
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::status;
+use crate::engine::eval;
+use crate::engine::game::Game;
+use crate::engine::search::ordering::{MoveOrdering, MovePicker};
+use crate::engine::search::tt::{Bound, TranspositionTable};
+
+pub mod ordering;
+pub mod tt;
+#[cfg(test)]
+mod tests;
+
+/// The maximum depth of the search, in plies.
+pub const MAX_PLY: usize = 128;
+/// The score of a checkmate at the root, mates further away score less.
+pub const MATE_SCORE: i32 = 30000;
+/// Scores of this value or above are mate scores.
+pub const MATE_BOUND: i32 = MATE_SCORE - MAX_PLY as i32;
+/// A score larger than any other score.
+pub const INFINITY: i32 = 32000;
+/// The score of a drawn position.
+const DRAW_SCORE: i32 = 0;
+/// The number of nodes searched between checking the time and the stop flag.
+const CHECK_INTERVAL: u64 = 1024;
+/// The margin of delta pruning: captures which cannot raise alpha even with this bonus are skipped.
+const DELTA_MARGIN: i32 = 200;
+
+/// The score of a position, from the point of view of the player who have to move.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Score {
+    /// The evaluation in centipawns.
+    Centipawns(i32),
+    /// Mate in the specified number of moves, negative if the player who have to move is mated.
+    Mate(i32),
+}
+
+impl Score {
+    /// Converts a score of the search to a `Score`, mate scores are converted to the number of moves.
+    pub fn from_value(value: i32) -> Score {
+        if value >= MATE_BOUND {
+            Score::Mate((MATE_SCORE - value + 1) / 2)
+        } else if value <= -MATE_BOUND {
+            Score::Mate(-(MATE_SCORE + value) / 2)
+        } else {
+            Score::Centipawns(value)
+        }
+    }
+}
+
+impl Display for Score {
+    /// Writes the score like the UCI protocol, e.g. "cp 35" or "mate -2".
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Score::Centipawns(score) => write!(f, "cp {}", score),
+            Score::Mate(moves) => write!(f, "mate {}", moves),
+        }
+    }
+}
+
+/// The limits of the search. The search stops when any of the limits is reached.
+///
+/// Without limits the search runs until the maximum depth, or until it finds a mate.
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    depth: Option<u8>,
+    nodes: Option<u64>,
+    movetime: Option<Duration>,
+    stop: Option<Arc<AtomicBool>>,
+}
+
+impl SearchLimits {
+    /// Creates limits which do not limit the search.
+    pub fn new() -> SearchLimits {
+        SearchLimits::default()
+    }
+
+    /// Limits the depth of the search, in plies.
+    pub fn set_depth(&mut self, depth: u8) -> &mut Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Limits the number of nodes searched.
+    pub fn set_nodes(&mut self, nodes: u64) -> &mut Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    /// Limits the time of the search.
+    pub fn set_movetime(&mut self, movetime: Duration) -> &mut Self {
+        self.movetime = Some(movetime);
+        self
+    }
+
+    /// Sets a flag which stops the search when it is set, for example from another thread.
+    pub fn set_stop(&mut self, stop: Arc<AtomicBool>) -> &mut Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Returns the maximum depth of the search.
+    pub fn get_depth(&self) -> Option<u8> {
+        self.depth
+    }
+
+    /// Returns the maximum number of nodes searched.
+    pub fn get_nodes(&self) -> Option<u64> {
+        self.nodes
+    }
+
+    /// Returns the maximum time of the search.
+    pub fn get_movetime(&self) -> Option<Duration> {
+        self.movetime
+    }
+}
+
+/// The result of a search.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchResult {
+    best_move: Option<ChessMove>,
+    score: Score,
+    pv: Vec<ChessMove>,
+    depth: u8,
+    nodes: u64,
+}
+
+impl SearchResult {
+    /// Returns the best move, or `None` if the player who have to move has no legal moves.
+    pub fn get_best_move(&self) -> Option<ChessMove> {
+        self.best_move
+    }
+
+    /// Returns the score of the best move.
+    pub fn get_score(&self) -> Score {
+        self.score
+    }
+
+    /// Returns the principal variation: the best move followed by the expected replies.
+    pub fn get_pv(&self) -> &[ChessMove] {
+        &self.pv
+    }
+
+    /// Returns the depth of the last completed iteration.
+    pub fn get_depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the number of nodes searched.
+    pub fn get_nodes(&self) -> u64 {
+        self.nodes
+    }
+}
+
+/// Searches the best move of the position within the limits.
+pub fn search(board: &Board, limits: &SearchLimits) -> SearchResult {
+    Search::new().search(board, limits)
+}
+
+/// The state of a search.
+pub struct Search {
+    limits: SearchLimits,
+    start: Instant,
+    nodes: u64,
+    stopped: bool,
+    /// The keys of the positions played in the game before the root, oldest first.
+    history: Vec<u64>,
+    /// The keys of the positions of the game and from the root to the current node, to detect
+    /// repetitions.
+    keys: Vec<u64>,
+    /// The moves from the root to the current node.
+    moves: Vec<ChessMove>,
+    /// Whether the quiescence search also follows the checking moves at its' first ply.
+    quiescence_checks: bool,
+    table: TranspositionTable,
+    ordering: MoveOrdering,
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Search::new()
+    }
+}
+
+impl Search {
+    /// Creates a new search.
+    pub fn new() -> Search {
+        Search {
+            limits: SearchLimits::new(),
+            start: Instant::now(),
+            nodes: 0,
+            stopped: false,
+            history: Vec::new(),
+            keys: Vec::new(),
+            moves: Vec::new(),
+            quiescence_checks: false,
+            table: TranspositionTable::default(),
+            ordering: MoveOrdering::new(),
+        }
+    }
+
+    /// Replaces the transposition table with an empty table of the size in megabytes.
+    pub fn set_table_size(&mut self, megabytes: usize) -> &mut Self {
+        self.table = TranspositionTable::new(megabytes);
+        self
+    }
+
+    /// Returns the transposition table.
+    pub fn get_table(&self) -> &TranspositionTable {
+        &self.table
+    }
+
+    /// Returns the transposition table mutably, for example to clear it before a new game.
+    pub fn get_table_mut(&mut self) -> &mut TranspositionTable {
+        &mut self.table
+    }
+
+    /// Sets whether the quiescence search also follows the quiet checking moves at its' first ply.
+    pub fn set_quiescence_checks(&mut self, quiescence_checks: bool) -> &mut Self {
+        self.quiescence_checks = quiescence_checks;
+        self
+    }
+
+    /// Sets the keys of the positions played in the game before the searched position, oldest
+    /// first, see `Board::get_key()`. Repeating any of them is scored as a draw.
+    pub fn set_history(&mut self, keys: &[u64]) -> &mut Self {
+        self.history = keys.to_vec();
+        self
+    }
+
+    /// Searches the best move of the current position of the game within the limits, repeating the
+    /// earlier positions of the game is scored as a draw.
+    pub fn search_game(&mut self, game: &Game, limits: &SearchLimits) -> SearchResult {
+        let keys: Vec<u64> = (0..game.get_ply())
+            .filter_map(|ply| game.position_at(ply))
+            .map(|board| board.get_key())
+            .collect();
+        self.set_history(&keys);
+        self.search(&game.get_board(), limits)
+    }
+
+    /// Searches the best move of the position within the limits.
+    ///
+    /// The position is searched after the positions of the history, see `set_history()`.
+    pub fn search(&mut self, board: &Board, limits: &SearchLimits) -> SearchResult {
+        self.limits = limits.clone();
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.stopped = false;
+        self.keys.clone_from(&self.history);
+        self.moves.clear();
+        self.table.new_search();
+        self.ordering.new_search();
+
+        let mut result = SearchResult {
+            best_move: None,
+            score: Score::Centipawns(DRAW_SCORE),
+            pv: Vec::new(),
+            depth: 0,
+            nodes: 0,
+        };
+
+        let moves = board.legal_moves();
+        if moves.is_empty() {
+            let value = if board.is_check() { -MATE_SCORE } else { DRAW_SCORE };
+            result.score = Score::from_value(value);
+            return result;
+        }
+
+        let max_depth = limits.depth.unwrap_or(MAX_PLY as u8 - 1).clamp(1, MAX_PLY as u8 - 1);
+        for depth in 1..=max_depth {
+            let mut pv = Vec::new();
+            let value = self.negamax(board, depth, -INFINITY, INFINITY, 0, &mut pv);
+
+            // An interrupted iteration is only used if there is no completed one
+            if pv.is_empty() || (self.stopped && depth > 1) {
+                break;
+            }
+
+            result.best_move = Some(pv[0]);
+            result.score = Score::from_value(value);
+            result.pv = pv.clone();
+            result.depth = depth;
+
+            // A deeper search cannot find a faster mate
+            let mate_found = value.abs() >= MATE_BOUND && MATE_SCORE - value.abs() <= depth as i32;
+            if self.stopped || mate_found || self.is_half_time_used() {
+                break;
+            }
+        }
+
+        if result.best_move.is_none() {
+            result.best_move = Some(moves[0]);
+            result.pv = vec![moves[0]];
+            result.score = Score::from_value(-eval::evaluate(&board.make_move(moves[0]).unwrap()));
+        }
+        result.nodes = self.nodes;
+        result
+    }
+
+    /// Searches the position to the depth, and returns the score within the `alpha`..`beta` window.
+    ///
+    /// The principal variation of the position is written to `pv`.
+    fn negamax(&mut self, board: &Board, depth: u8, mut alpha: i32, beta: i32, ply: usize, pv: &mut Vec<ChessMove>) -> i32 {
+        if depth == 0 {
+            return self.quiescence(board, alpha, beta, ply, 0, pv);
+        }
+        pv.clear();
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+        self.nodes += 1;
+
+        if ply > 0 && self.is_draw(board) {
+            return DRAW_SCORE;
+        }
+        if ply >= MAX_PLY - 1 {
+            return eval::evaluate(board);
+        }
+
+        let entry = self.table.probe(board.get_key());
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.get_depth() >= depth) {
+            let score = entry.get_score(ply);
+            let cutoff = match entry.get_bound() {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+            if cutoff {
+                // Only an exact score can become part of the principal variation
+                if entry.get_bound() == Bound::Exact {
+                    self.table_pv(board, depth, pv);
+                }
+                return score;
+            }
+        }
+
+        let previous = self.moves.last().copied();
+        let mut picker = MovePicker::new(board, entry.and_then(|entry| entry.get_move()), &self.ordering, ply, previous);
+
+        let original_alpha = alpha;
+        let mut best = -INFINITY;
+        let mut best_move = None;
+        let mut quiets = Vec::new();
+        let mut child_pv = Vec::new();
+        self.keys.push(board.get_key());
+
+        while let Some(chess_move) = picker.next_move(&self.ordering) {
+            let child = board.make_move(chess_move).unwrap();
+            self.moves.push(chess_move);
+            let value = -self.negamax(&child, depth - 1, -beta, -alpha, ply + 1, &mut child_pv);
+            self.moves.pop();
+            if self.stopped {
+                break;
+            }
+
+            let quiet = ordering::is_quiet(board, chess_move);
+            if value > best {
+                best = value;
+                if value > alpha {
+                    alpha = value;
+                    best_move = Some(chess_move);
+                    pv.clear();
+                    pv.push(chess_move);
+                    pv.extend_from_slice(&child_pv);
+                    if alpha >= beta {
+                        if quiet {
+                            self.ordering.update(board.get_turn(), chess_move, previous, ply, depth, &quiets);
+                        }
+                        break;
+                    }
+                }
+            }
+            if quiet {
+                quiets.push(chess_move);
+            }
+        }
+
+        self.keys.pop();
+        if best == -INFINITY && !self.stopped {
+            // No legal moves
+            return if board.is_check() { -MATE_SCORE + ply as i32 } else { DRAW_SCORE };
+        }
+        if !self.stopped {
+            let bound = if best >= beta {
+                Bound::Lower
+            } else if best > original_alpha {
+                Bound::Exact
+            } else {
+                Bound::Upper
+            };
+            self.table.store(board.get_key(), best_move, best, depth, bound, ply);
+        }
+        best
+    }
+
+    /// Searches the captures and queen promotions of the position, until it is quiet.
+    ///
+    /// The player who have to move may stand pat: decline every capture and take the static
+    /// evaluation, unless in check, where every evasion is searched. `depth` is the number of plies
+    /// searched in the quiescence search.
+    fn quiescence(&mut self, board: &Board, mut alpha: i32, beta: i32, ply: usize, depth: usize, pv: &mut Vec<ChessMove>) -> i32 {
+        pv.clear();
+        if self.should_stop() {
+            return DRAW_SCORE;
+        }
+        self.nodes += 1;
+
+        if ply > 0 && self.is_draw(board) {
+            return DRAW_SCORE;
+        }
+        if ply >= MAX_PLY - 1 {
+            return eval::evaluate(board);
+        }
+
+        let in_check = board.is_check();
+        let stand_pat = eval::evaluate(board);
+        let mut best = -INFINITY;
+        let mut moves = if in_check {
+            let evasions = board.legal_moves();
+            if evasions.is_empty() {
+                return -MATE_SCORE + ply as i32;
+            }
+            evasions
+        } else {
+            if stand_pat >= beta {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+            best = stand_pat;
+
+            let mut moves = board.legal_captures();
+            if self.quiescence_checks && depth == 0 {
+                moves.extend(board.legal_moves()
+                    .into_iter()
+                    .filter(|chess_move| board.get_captured_piece(*chess_move).is_none() && chess_move.get_promotion().is_none())
+                    .filter(|chess_move| board.make_move(*chess_move).unwrap().is_check()));
+            }
+            moves
+        };
+        // The most valuable victims are captured first, by the least valuable attackers
+        moves.sort_by_key(|chess_move| -ordering::mvv_lva(board, *chess_move));
+
+        let mut child_pv = Vec::new();
+        self.keys.push(board.get_key());
+
+        for chess_move in moves {
+            // Delta pruning: skip the captures which cannot raise alpha, even with a positional gain
+            let captured = board.get_captured_piece(chess_move);
+            if !in_check && chess_move.get_promotion().is_none() {
+                let gain = captured.map_or(0, |piece| piece.get_value());
+                if captured.is_some() && stand_pat + gain + DELTA_MARGIN <= alpha {
+                    continue;
+                }
+            }
+            // The captures which lose material by the static exchange evaluation are not searched
+            if !in_check && captured.is_some() && !board.see_ge(chess_move, 0) {
+                continue;
+            }
+
+            let child = board.make_move(chess_move).unwrap();
+            let value = -self.quiescence(&child, -beta, -alpha, ply + 1, depth + 1, &mut child_pv);
+            if self.stopped {
+                break;
+            }
+
+            if value > best {
+                best = value;
+                if value > alpha {
+                    alpha = value;
+                    pv.clear();
+                    pv.push(chess_move);
+                    pv.extend_from_slice(&child_pv);
+                    if alpha >= beta {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.keys.pop();
+        best
+    }
+
+    /// Writes the principal variation of a position cut off by the transposition table: the best
+    /// moves of the table, as long as they are legal, up to the depth.
+    fn table_pv(&self, board: &Board, depth: u8, pv: &mut Vec<ChessMove>) {
+        let mut board = *board;
+        for _ in 0..depth {
+            let chess_move = match self.table.probe(board.get_key()).and_then(|entry| entry.get_move()) {
+                Some(chess_move) => chess_move,
+                None => break,
+            };
+            board = match board.make_move(chess_move) {
+                Some(next) => next,
+                None => break,
+            };
+            pv.push(chess_move);
+        }
+    }
+
+    /// Checks whether the position is a draw by the fifty-move rule, by insufficient material or by
+    /// repeating a position of the game or the search.
+    fn is_draw(&self, board: &Board) -> bool {
+        if status::is_insufficient_material(board) {
+            return true;
+        }
+        // Checkmate takes precedence over the fifty-move rule
+        if board.get_half_moves() >= 100 {
+            return !board.is_check() || !board.legal_moves().is_empty();
+        }
+
+        // Only the positions since the last capture or pawn move can repeat, with the same side to move
+        let key = board.get_key();
+        self.keys.iter()
+            .rev()
+            .take(board.get_half_moves() as usize)
+            .skip(1)
+            .step_by(2)
+            .any(|&previous| previous == key)
+    }
+
+    /// Checks the limits of the search, the time and the stop flag are only checked periodically.
+    fn should_stop(&mut self) -> bool {
+        if self.stopped {
+            return true;
+        }
+
+        if self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes) {
+            self.stopped = true;
+        } else if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            let timeout = self.limits.movetime.is_some_and(|movetime| self.start.elapsed() >= movetime);
+            let stop = self.limits.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed));
+            self.stopped = timeout || stop;
+        }
+
+        self.stopped
+    }
+
+    /// Checks whether half of the time is used, so the next iteration would probably not complete.
+    fn is_half_time_used(&self) -> bool {
+        self.limits.movetime.is_some_and(|movetime| self.start.elapsed() >= movetime / 2)
+    }
+}
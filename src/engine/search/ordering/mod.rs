@@ -0,0 +1,302 @@
+//! This module implements the move ordering of the search.
+//!
+//! Alpha-beta search cuts off more branches when the best move is searched first. The moves are
+//! returned by a `MovePicker` in stages, from the most to the least promising:
+//!
+//! 1. the best move of the transposition table
+//! 2. the captures and queen promotions which do not lose material by the static exchange
+//!    evaluation, the most valuable victims by the least valuable attackers first (MVV-LVA)
+//! 3. the killer moves: quiet moves which caused a cutoff at the same ply
+//! 4. the countermove: the quiet move which caused a cutoff after the previous move
+//! 5. the other quiet moves, by their' history: how often they caused cutoffs in the search
+//! 6. the captures which lose material
+//!
+//! The moves of a stage are only generated when the stage is reached, and the best one is selected
+//! when it is needed, so the moves after a cutoff are never generated or sorted.
+
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::board::piece::Piece;
+use crate::engine::search::MAX_PLY;
+
+#[cfg(test)]
+mod tests;
+
+/// The limit of the history scores, the scores converge to it instead of overflowing.
+pub const MAX_HISTORY: i32 = 16384;
+/// The number of killer moves of a ply.
+const KILLERS: usize = 2;
+
+/// The heuristics which learn the good quiet moves during the search.
+#[derive(Debug, Clone)]
+pub struct MoveOrdering {
+    /// The quiet moves which caused the latest cutoffs, by ply.
+    killers: Vec<[Option<ChessMove>; KILLERS]>,
+    /// The butterfly history of the quiet moves, by color, source and destination.
+    history: Box<[[[i32; 64]; 64]; 2]>,
+    /// The quiet moves which refuted a move, by the source and the destination of the move.
+    countermoves: Box<[[Option<ChessMove>; 64]; 64]>,
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        MoveOrdering::new()
+    }
+}
+
+impl MoveOrdering {
+    /// Creates the heuristics with no knowledge about the moves.
+    pub fn new() -> MoveOrdering {
+        MoveOrdering {
+            killers: vec![[None; KILLERS]; MAX_PLY],
+            history: Box::new([[[0; 64]; 64]; 2]),
+            countermoves: Box::new([[None; 64]; 64]),
+        }
+    }
+
+    /// Removes everything learned.
+    pub fn clear(&mut self) {
+        *self = MoveOrdering::new();
+    }
+
+    /// Starts a new search: the killer moves are removed, and the history scores are halved, so
+    /// the new results matter more.
+    pub fn new_search(&mut self) {
+        self.killers.fill([None; KILLERS]);
+        self.history.iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|score| *score /= 2);
+    }
+
+    /// Returns the killer moves of the ply, the latest one first.
+    pub fn get_killers(&self, ply: usize) -> [Option<ChessMove>; KILLERS] {
+        self.killers[ply]
+    }
+
+    /// Returns the history score of the quiet move of the color.
+    pub fn get_history(&self, color: Color, chess_move: ChessMove) -> i32 {
+        self.history[color.to_index()][chess_move.get_source().to_index() as usize][chess_move.get_destination().to_index() as usize]
+    }
+
+    /// Returns the countermove of the move.
+    pub fn get_countermove(&self, previous: ChessMove) -> Option<ChessMove> {
+        self.countermoves[previous.get_source().to_index() as usize][previous.get_destination().to_index() as usize]
+    }
+
+    /// Learns from the quiet move of the color, which caused a cutoff at the ply and depth.
+    ///
+    /// The move becomes a killer move and the countermove of the previous move, its' history score
+    /// is increased, and the history scores of the quiet moves searched before it are decreased.
+    pub fn update(&mut self, color: Color, chess_move: ChessMove, previous: Option<ChessMove>, ply: usize, depth: u8, searched: &[ChessMove]) {
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(chess_move) {
+            killers[1] = killers[0];
+            killers[0] = Some(chess_move);
+        }
+
+        if let Some(previous) = previous {
+            self.countermoves[previous.get_source().to_index() as usize][previous.get_destination().to_index() as usize] = Some(chess_move);
+        }
+
+        let bonus = (i32::from(depth) * i32::from(depth)).min(MAX_HISTORY);
+        self.add_history(color, chess_move, bonus);
+        for &quiet in searched.iter().filter(|quiet| **quiet != chess_move) {
+            self.add_history(color, quiet, -bonus);
+        }
+    }
+
+    /// Adds the bonus to the history score, the larger the score the smaller the change.
+    fn add_history(&mut self, color: Color, chess_move: ChessMove, bonus: i32) {
+        let score = &mut self.history[color.to_index()][chess_move.get_source().to_index() as usize][chess_move.get_destination().to_index() as usize];
+        *score += bonus - *score * bonus.abs() / MAX_HISTORY;
+    }
+}
+
+/// Checks whether the move is quiet: it is not a capture or a promotion.
+pub fn is_quiet(board: &Board, chess_move: ChessMove) -> bool {
+    chess_move.get_promotion().is_none() && board.get_captured_piece(chess_move).is_none()
+}
+
+/// Scores a capture or a promotion by MVV-LVA: the value of the captured and the promoted piece,
+/// by the value of the moving piece. The king counts as the least valuable attacker, it can only
+/// capture undefended pieces.
+pub fn mvv_lva(board: &Board, chess_move: ChessMove) -> i32 {
+    let victim = board.get_captured_piece(chess_move).map_or(0, |piece| piece.get_value());
+    let promotion = chess_move.get_promotion().map_or(0, |piece| piece.get_value() - Piece::Pawn.get_value());
+    let attacker = match board.piece_at(chess_move.get_source(), board.get_turn()) {
+        Some(Piece::King) | None => 0,
+        Some(piece) => piece.get_value(),
+    };
+    (victim + promotion) * 100 - attacker
+}
+
+/// The stages of the `MovePicker`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Stage {
+    TableMove,
+    GenerateCaptures,
+    Captures,
+    Killers,
+    Countermove,
+    GenerateQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Returns the legal moves of a position in stages, see the module documentation.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut picker = MovePicker::new(&board, table_move, &ordering, ply, previous);
+/// while let Some(chess_move) = picker.next_move(&ordering) {
+///     // search the move
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MovePicker<'a> {
+    board: &'a Board,
+    stage: Stage,
+    table_move: Option<ChessMove>,
+    killers: [Option<ChessMove>; KILLERS],
+    countermove: Option<ChessMove>,
+    /// The generated moves of the current stage, with their' scores.
+    moves: Vec<(ChessMove, i32)>,
+    /// The captures which lose material, they are returned after the quiet moves.
+    bad_captures: Vec<ChessMove>,
+    /// The moves returned so far, they are not returned again by the later stages.
+    returned: Vec<ChessMove>,
+    /// The legal moves of the position, generated when a move from another position is checked.
+    legal: Option<Vec<ChessMove>>,
+}
+
+impl<'a> MovePicker<'a> {
+    /// Creates a picker for the position, with the best move of the transposition table, and the
+    /// killers and the countermove of the heuristics for the ply and the previous move.
+    pub fn new(board: &'a Board, table_move: Option<ChessMove>, ordering: &MoveOrdering, ply: usize, previous: Option<ChessMove>) -> MovePicker<'a> {
+        MovePicker {
+            board,
+            stage: Stage::TableMove,
+            table_move,
+            killers: ordering.get_killers(ply),
+            countermove: previous.and_then(|previous| ordering.get_countermove(previous)),
+            moves: Vec::new(),
+            bad_captures: Vec::new(),
+            returned: Vec::new(),
+            legal: None,
+        }
+    }
+
+    /// Returns the next move, or `None` if every legal move was returned.
+    ///
+    /// The quiet moves are ordered by the history scores of the heuristics.
+    pub fn next_move(&mut self, ordering: &MoveOrdering) -> Option<ChessMove> {
+        loop {
+            let chess_move = match self.stage {
+                Stage::TableMove => {
+                    let table_move = self.table_move;
+                    table_move.filter(|chess_move| self.is_legal(*chess_move))
+                }
+                Stage::GenerateCaptures => {
+                    self.moves = self.board.legal_captures()
+                        .into_iter()
+                        .filter(|chess_move| !self.returned.contains(chess_move))
+                        .map(|chess_move| (chess_move, mvv_lva(self.board, chess_move)))
+                        .collect();
+                    None
+                }
+                Stage::Captures => match self.select() {
+                    Some(chess_move) if !self.board.see_ge(chess_move, 0) => {
+                        self.bad_captures.push(chess_move);
+                        continue;
+                    }
+                    Some(chess_move) => return Some(self.returned(chess_move)),
+                    None => None,
+                },
+                Stage::Killers => {
+                    let killers = self.killers;
+                    match killers.iter().flatten().copied().find(|killer| self.is_quiet_candidate(*killer)) {
+                        Some(killer) => return Some(self.returned(killer)),
+                        None => None,
+                    }
+                }
+                Stage::Countermove => {
+                    let countermove = self.countermove;
+                    countermove.filter(|chess_move| self.is_quiet_candidate(*chess_move))
+                }
+                Stage::GenerateQuiets => {
+                    let color = self.board.get_turn();
+                    let legal = self.legal.take().unwrap_or_else(|| self.board.legal_moves());
+                    self.moves = legal
+                        .into_iter()
+                        .filter(|chess_move| !self.returned.contains(chess_move) && !self.bad_captures.contains(chess_move))
+                        .map(|chess_move| {
+                            // Under-promotions are only searched after all the other moves
+                            let score = match chess_move.get_promotion() {
+                                Some(_) => -2 * MAX_HISTORY,
+                                None => ordering.get_history(color, chess_move),
+                            };
+                            (chess_move, score)
+                        })
+                        .collect();
+                    None
+                }
+                Stage::Quiets => match self.select() {
+                    Some(chess_move) => return Some(self.returned(chess_move)),
+                    None => None,
+                },
+                Stage::BadCaptures => {
+                    if !self.bad_captures.is_empty() {
+                        let chess_move = self.bad_captures.remove(0);
+                        return Some(self.returned(chess_move));
+                    }
+                    None
+                }
+                Stage::Done => return None,
+            };
+
+            self.stage = match self.stage {
+                Stage::TableMove => Stage::GenerateCaptures,
+                Stage::GenerateCaptures => Stage::Captures,
+                Stage::Captures => Stage::Killers,
+                Stage::Killers => Stage::Countermove,
+                Stage::Countermove => Stage::GenerateQuiets,
+                Stage::GenerateQuiets => Stage::Quiets,
+                Stage::Quiets => Stage::BadCaptures,
+                Stage::BadCaptures | Stage::Done => Stage::Done,
+            };
+            if let Some(chess_move) = chess_move {
+                return Some(self.returned(chess_move));
+            }
+        }
+    }
+
+    /// Removes and returns the generated move with the highest score.
+    fn select(&mut self) -> Option<ChessMove> {
+        let index = self.moves.iter()
+            .enumerate()
+            .max_by_key(|(_, (_, score))| *score)
+            .map(|(index, _)| index)?;
+        Some(self.moves.swap_remove(index).0)
+    }
+
+    /// Records that the move is returned, and returns it.
+    fn returned(&mut self, chess_move: ChessMove) -> ChessMove {
+        self.returned.push(chess_move);
+        chess_move
+    }
+
+    /// Checks whether the move is a quiet legal move, which was not returned yet.
+    fn is_quiet_candidate(&mut self, chess_move: ChessMove) -> bool {
+        !self.returned.contains(&chess_move) && is_quiet(self.board, chess_move) && self.is_legal(chess_move)
+    }
+
+    /// Checks whether the move, which may come from another position, is one of the legal moves.
+    fn is_legal(&mut self, chess_move: ChessMove) -> bool {
+        let board = self.board;
+        self.legal.get_or_insert_with(|| board.legal_moves()).contains(&chess_move)
+    }
+}
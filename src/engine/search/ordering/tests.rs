@@ -0,0 +1,122 @@
+use crate::engine::board::Board;
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::color::Color;
+use crate::engine::fen;
+use crate::engine::search::ordering;
+use crate::engine::search::ordering::{MoveOrdering, MovePicker, MAX_HISTORY};
+
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+fn picked_moves(board: &Board, table_move: Option<ChessMove>, ordering: &MoveOrdering, ply: usize, previous: Option<ChessMove>) -> Vec<String> {
+    let mut picker = MovePicker::new(board, table_move, ordering, ply, previous);
+    let mut moves = Vec::new();
+    while let Some(chess_move) = picker.next_move(ordering) {
+        moves.push(chess_move.to_string());
+    }
+    moves
+}
+
+#[test]
+fn picks_every_legal_move_once() {
+    let board = fen::from_fen(KIWIPETE).unwrap();
+    let table_move = board.parse_uci("e1g1").ok();
+    let moves = picked_moves(&board, table_move, &MoveOrdering::new(), 0, None);
+
+    assert_eq!(48, moves.len());
+    let mut legal: Vec<String> = board.legal_moves().iter().map(|chess_move| chess_move.to_string()).collect();
+    let mut sorted = moves.clone();
+    legal.sort();
+    sorted.sort();
+    assert_eq!(legal, sorted);
+
+    // The table move, then the captures by MVV-LVA: the bishop first, then the pawns by pawns
+    assert_eq!("e1g1", moves[0]);
+    assert_eq!("e2a6", moves[1]);
+    let mut pawn_captures = moves[2..4].to_vec();
+    pawn_captures.sort();
+    assert_eq!(vec!["d5e6", "g2h3"], pawn_captures);
+
+    // The captures which lose material come last, Qxf6 is recaptured by the bishop
+    let position = |uci: &str| moves.iter().position(|chess_move| chess_move == uci).unwrap();
+    assert_eq!(43, position("f3f6"));
+    assert!(moves[43..].iter().all(|chess_move| board.get_captured_piece(board.parse_uci(chess_move).unwrap()).is_some()));
+}
+
+#[test]
+fn illegal_table_move_is_skipped() {
+    let board = Board::new();
+    let table_move = "e2e5".parse().ok();
+    let moves = picked_moves(&board, table_move, &MoveOrdering::new(), 0, None);
+    assert_eq!(20, moves.len());
+    assert!(!moves.contains(&"e2e5".to_owned()));
+}
+
+#[test]
+fn promotion_without_piece_is_skipped() {
+    // The pawn move to the last rank without a promotion piece is not legal
+    let board = fen::from_fen("7k/4P3/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let e7e8: ChessMove = "e7e8".parse().unwrap();
+    let previous: ChessMove = "g8h8".parse().unwrap();
+    let mut ordering = MoveOrdering::new();
+    ordering.update(Color::White, e7e8, Some(previous), 0, 1, &[]);
+
+    let moves = picked_moves(&board, Some(e7e8), &ordering, 0, Some(previous));
+    let mut legal: Vec<String> = board.legal_moves().iter().map(|chess_move| chess_move.to_string()).collect();
+    let mut sorted = moves.clone();
+    legal.sort();
+    sorted.sort();
+    assert_eq!(7, moves.len());
+    assert_eq!(legal, sorted);
+}
+
+#[test]
+fn killers_countermoves_and_history() {
+    let board = Board::new();
+    let previous: ChessMove = "e7e5".parse().unwrap();
+    let mut ordering = MoveOrdering::new();
+
+    let searched: Vec<ChessMove> = vec!["a2a3".parse().unwrap(), "b2b3".parse().unwrap()];
+    ordering.update(Color::White, "g1f3".parse().unwrap(), None, 3, 4, &searched);
+    ordering.update(Color::White, "d2d4".parse().unwrap(), Some(previous), 3, 2, &[]);
+    ordering.update(Color::White, "h2h3".parse().unwrap(), None, 5, 6, &[]);
+    assert_eq!(16, ordering.get_history(Color::White, "g1f3".parse().unwrap()));
+    assert!(ordering.get_history(Color::White, "a2a3".parse().unwrap()) < 0);
+    assert_eq!(0, ordering.get_history(Color::Black, "g1f3".parse().unwrap()));
+
+    // The killers of the ply, the latest first, then the countermove, then the history
+    let moves = picked_moves(&board, None, &ordering, 3, Some(previous));
+    assert_eq!(vec!["d2d4", "g1f3", "h2h3"], moves[..3].to_vec());
+    let mut last = moves[18..].to_vec();
+    last.sort();
+    assert_eq!(vec!["a2a3", "b2b3"], last);
+
+    let moves = picked_moves(&board, None, &ordering, 5, None);
+    assert_eq!("h2h3", moves[0]);
+
+    ordering.new_search();
+    assert_eq!([None, None], ordering.get_killers(3));
+    assert_eq!(8, ordering.get_history(Color::White, "g1f3".parse().unwrap()));
+
+    ordering.clear();
+    assert_eq!(None, ordering.get_countermove(previous));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut ordering = MoveOrdering::new();
+    let chess_move: ChessMove = "e2e4".parse().unwrap();
+    for _ in 0..1000 {
+        ordering.update(Color::Black, chess_move, None, 0, 100, &[]);
+    }
+    assert!(ordering.get_history(Color::Black, chess_move) <= MAX_HISTORY);
+}
+
+#[test]
+fn mvv_lva() {
+    let board = fen::from_fen("4k3/8/8/1q1r4/2P1P3/3Q4/8/4K3 w - - 0 1").unwrap();
+    let score = |uci: &str| ordering::mvv_lva(&board, board.parse_uci(uci).unwrap());
+    // The queen before the rook, by the pawn before the queen
+    assert!(score("c4b5") > score("c4d5"));
+    assert_eq!(score("c4d5"), score("e4d5"));
+    assert!(score("e4d5") > score("d3d5"));
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
+
+use crate::engine::board::Board;
+use crate::engine::fen;
+use crate::engine::game::Game;
+use crate::engine::search;
+use crate::engine::search::{Score, Search, SearchLimits, MATE_SCORE};
+
+fn search_depth(fen: &str, depth: u8) -> search::SearchResult {
+    let mut limits = SearchLimits::new();
+    limits.set_depth(depth);
+    search::search(&fen::from_fen(fen).unwrap(), &limits)
+}
+
+#[test]
+fn score_from_value() {
+    assert_eq!(Score::Centipawns(-35), Score::from_value(-35));
+    assert_eq!(Score::Mate(1), Score::from_value(MATE_SCORE - 1));
+    assert_eq!(Score::Mate(2), Score::from_value(MATE_SCORE - 3));
+    assert_eq!(Score::Mate(-1), Score::from_value(-MATE_SCORE + 2));
+    assert_eq!("mate -1", Score::from_value(-MATE_SCORE + 2).to_string());
+    assert_eq!("cp 12", Score::Centipawns(12).to_string());
+}
+
+#[test]
+fn mate_in_one() {
+    let result = search_depth("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 4);
+    assert_eq!("a1a8", result.get_best_move().unwrap().to_string());
+    assert_eq!(Score::Mate(1), result.get_score());
+    assert_eq!(1, result.get_pv().len());
+
+    // Scholar's mate
+    let result = search_depth("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", 4);
+    assert_eq!("h5f7", result.get_best_move().unwrap().to_string());
+    assert_eq!(Score::Mate(1), result.get_score());
+}
+
+#[test]
+fn mate_in_two() {
+    // 1. Ra6 bxa6 2. b7#
+    let result = search_depth("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1", 4);
+    assert_eq!("a1a6", result.get_best_move().unwrap().to_string());
+    assert_eq!(Score::Mate(2), result.get_score());
+    assert_eq!(3, result.get_pv().len());
+}
+
+#[test]
+fn mate_on_the_fiftieth_move() {
+    // The mating move is the 100th half move without a capture or a pawn move
+    let result = search_depth("6k1/5ppp/8/8/8/8/8/R5K1 w - - 99 80", 3);
+    assert_eq!("a1a8", result.get_best_move().unwrap().to_string());
+    assert_eq!(Score::Mate(1), result.get_score());
+}
+
+#[test]
+fn game_repetition_is_draw() {
+    // Down a queen, Black repeats the initial position of the game with Ka8
+    let mut game = Game::from_board(fen::from_fen("k7/8/8/8/8/8/8/3Q3K w - - 0 1").unwrap());
+    for uci in &["d1d2", "a8b8", "d2d1"] {
+        let chess_move = game.get_board().parse_uci(uci).unwrap();
+        game.push(chess_move);
+    }
+
+    let mut limits = SearchLimits::new();
+    limits.set_depth(3);
+    let result = Search::new().search_game(&game, &limits);
+    assert_eq!("b8a8", result.get_best_move().unwrap().to_string());
+    assert_eq!(Score::Centipawns(0), result.get_score());
+
+    // Without the history the repetition is not seen
+    let result = Search::new().search(&game.get_board(), &limits);
+    assert!(result.get_score() != Score::Centipawns(0));
+}
+
+#[test]
+fn principal_variation_is_legal() {
+    let result = search_depth("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3", 3);
+    assert_eq!(3, result.get_depth());
+    assert_eq!(result.get_best_move(), result.get_pv().first().copied());
+
+    let mut board = fen::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+    for &chess_move in result.get_pv() {
+        board = board.make_move(chess_move).unwrap();
+    }
+}
+
+#[test]
+fn captures_hanging_queen() {
+    let result = search_depth("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1", 2);
+    assert_eq!("d1d5", result.get_best_move().unwrap().to_string());
+}
+
+#[test]
+fn no_legal_moves() {
+    let checkmate = search_depth("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1", 3);
+    assert_eq!(None, checkmate.get_best_move());
+    assert_eq!(Score::Mate(0), checkmate.get_score());
+
+    let stalemate = search_depth("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", 3);
+    assert_eq!(None, stalemate.get_best_move());
+    assert_eq!(Score::Centipawns(0), stalemate.get_score());
+}
+
+#[test]
+fn avoids_stalemate() {
+    // Qf7 stalemates, Qc8 and Qf8 mate
+    let board = fen::from_fen("7k/8/6K1/5Q2/8/8/8/8 w - - 0 1").unwrap();
+    let result = search_depth("7k/8/6K1/5Q2/8/8/8/8 w - - 0 1", 3);
+    let mated = board.make_move(result.get_best_move().unwrap()).unwrap();
+    assert!(mated.is_check() && mated.legal_moves().is_empty());
+    assert_eq!(Score::Mate(1), result.get_score());
+}
+
+#[test]
+fn node_limit() {
+    let mut limits = SearchLimits::new();
+    limits.set_nodes(500);
+    let result = search::search(&Board::new(), &limits);
+
+    assert!(result.get_best_move().is_some());
+    assert!(result.get_nodes() <= 500);
+}
+
+#[test]
+fn movetime_limit() {
+    let mut limits = SearchLimits::new();
+    limits.set_movetime(Duration::from_millis(100));
+    let start = Instant::now();
+    let result = search::search(&Board::new(), &limits);
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(result.get_depth() >= 1);
+}
+
+#[test]
+fn stop_flag() {
+    let mut limits = SearchLimits::new();
+    limits.set_stop(Arc::new(AtomicBool::new(true)));
+    let result = search::search(&Board::new(), &limits);
+
+    // The search is stopped at once, but it still returns a legal move
+    assert!(Board::new().legal_moves().contains(&result.get_best_move().unwrap()));
+}
+
+#[test]
+fn quiescence_sees_recapture() {
+    // Qxd5 wins a pawn, but the queen is lost to exd5 behind the horizon
+    let result = search_depth("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", 1);
+    assert_ne!("d1d5", result.get_best_move().unwrap().to_string());
+    assert_eq!(1, result.get_depth());
+}
+
+#[test]
+fn quiescence_checks() {
+    // Rxa7 wins a knight, but leaves the back rank to Re1#
+    let board = fen::from_fen("4r1k1/n4ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let mut limits = SearchLimits::new();
+    limits.set_depth(1);
+
+    let result = Search::new().search(&board, &limits);
+    assert_eq!("a1a7", result.get_best_move().unwrap().to_string());
+
+    let result = Search::new().set_quiescence_checks(true).search(&board, &limits);
+    assert_ne!("a1a7", result.get_best_move().unwrap().to_string());
+}
+
+#[test]
+fn transposition_table_is_reused() {
+    let board = fen::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+    let mut limits = SearchLimits::new();
+    limits.set_depth(4);
+
+    let mut search = Search::new();
+    search.set_table_size(1);
+    let first = search.search(&board, &limits);
+    assert!(search.get_table().get_hashfull() > 0);
+    assert_eq!(first.get_best_move(), search.get_table().probe(board.get_key()).unwrap().get_move());
+
+    // The second search is cut off by the results of the first one
+    let second = search.search(&board, &limits);
+    assert_eq!(first.get_best_move(), second.get_best_move());
+    assert!(second.get_nodes() < first.get_nodes());
+
+    search.get_table_mut().clear();
+    assert_eq!(None, search.get_table().probe(board.get_key()));
+}
@@ -0,0 +1,237 @@
+//! This module implements the transposition table, which stores the results of the search.
+//!
+//! The same position is often reached by different move orders. The table stores the result of
+//! every searched position by its' hash key, see `Board::get_key()`, so the search can reuse it:
+//! a deep enough result cuts off the search of the position, and the best move is searched first.
+//!
+//! The table is an array of buckets, the number of buckets is a power of two, so the bucket of a
+//! key is found by masking its' lower bits. Every bucket has two entries:
+//!
+//! * the depth-preferred entry, which is only replaced by deeper results, or by the results of a
+//!   newer search
+//! * the always-replace entry, which stores the results that do not replace the first one
+
+use std::mem;
+
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::Square;
+use crate::engine::search::MATE_BOUND;
+
+#[cfg(test)]
+mod tests;
+
+/// The default size of the table in megabytes.
+pub const DEFAULT_SIZE: usize = 16;
+/// The number of entries in a bucket.
+const BUCKET_SIZE: usize = 2;
+/// The maximum number of buckets sampled by `get_hashfull()`.
+const HASHFULL_SAMPLE: usize = 500;
+
+/// The relation of the stored score to the real score of the position.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Bound {
+    /// The score is exact, it was within the window of the search.
+    Exact,
+    /// The real score is at least the stored score, the search was cut off by a better move.
+    Lower,
+    /// The real score is at most the stored score, no move reached the window of the search.
+    Upper,
+}
+
+/// A result of the search stored in the table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Entry {
+    key: u64,
+    chess_move: u16,
+    score: i16,
+    depth: u8,
+    bound: Bound,
+    age: u8,
+}
+
+impl Entry {
+    /// Returns the best move of the position, or `None` if no move reached the window of the search.
+    pub fn get_move(&self) -> Option<ChessMove> {
+        decode_move(self.chess_move)
+    }
+
+    /// Returns the score of the position, searched from the specified ply.
+    ///
+    /// Mate scores are stored relative to the position, they are converted back to the distance
+    /// from the root.
+    pub fn get_score(&self, ply: usize) -> i32 {
+        let score = i32::from(self.score);
+        if score >= MATE_BOUND {
+            score - ply as i32
+        } else if score <= -MATE_BOUND {
+            score + ply as i32
+        } else {
+            score
+        }
+    }
+
+    /// Returns the depth of the search which found the result.
+    pub fn get_depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the bound type of the score.
+    pub fn get_bound(&self) -> Bound {
+        self.bound
+    }
+
+    /// Returns the age of the search which found the result.
+    pub fn get_age(&self) -> u8 {
+        self.age
+    }
+}
+
+/// The transposition table, see the module documentation.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    buckets: Vec<[Option<Entry>; BUCKET_SIZE]>,
+    age: u8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(DEFAULT_SIZE)
+    }
+}
+
+impl TranspositionTable {
+    /// Creates an empty table of at most the specified size in megabytes.
+    ///
+    /// The number of buckets is rounded down to a power of two, the table has at least one bucket.
+    pub fn new(megabytes: usize) -> TranspositionTable {
+        let count = megabytes * 1024 * 1024 / mem::size_of::<[Option<Entry>; BUCKET_SIZE]>();
+        let count = if count.is_power_of_two() { count } else { (count.next_power_of_two() / 2).max(1) };
+        TranspositionTable {
+            buckets: vec![[None; BUCKET_SIZE]; count],
+            age: 0,
+        }
+    }
+
+    /// Returns the number of entries of the table.
+    pub fn len(&self) -> usize {
+        self.buckets.len() * BUCKET_SIZE
+    }
+
+    /// Checks whether the table has no buckets. The stored entries are not counted, and a table
+    /// always has at least one bucket.
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Returns the age of the current search.
+    pub fn get_age(&self) -> u8 {
+        self.age
+    }
+
+    /// Starts a new search: the entries of the previous searches are replaced first.
+    pub fn new_search(&mut self) {
+        self.age = self.age.wrapping_add(1);
+    }
+
+    /// Removes all entries.
+    pub fn clear(&mut self) {
+        self.buckets.fill([None; BUCKET_SIZE]);
+        self.age = 0;
+    }
+
+    /// Returns the entry of the position with the key.
+    pub fn probe(&self, key: u64) -> Option<Entry> {
+        self.buckets[self.index(key)].iter()
+            .flatten()
+            .find(|entry| entry.key == key)
+            .copied()
+    }
+
+    /// Stores the result of the search of the position with the key, searched from the ply.
+    ///
+    /// If the result has no best move, the best move of the previous entry of the position is kept.
+    pub fn store(&mut self, key: u64, chess_move: Option<ChessMove>, score: i32, depth: u8, bound: Bound, ply: usize) {
+        let index = self.index(key);
+        let age = self.age;
+        let bucket = &mut self.buckets[index];
+
+        let matching = bucket.iter().position(|entry| entry.is_some_and(|entry| entry.key == key));
+        let previous = matching.and_then(|slot| bucket[slot]);
+        let chess_move = match chess_move {
+            Some(chess_move) => encode_move(chess_move),
+            None => previous.map_or(0, |entry| entry.chess_move),
+        };
+        // Mate scores are stored as the distance from this position, not from the root
+        let score = if score >= MATE_BOUND {
+            score + ply as i32
+        } else if score <= -MATE_BOUND {
+            score - ply as i32
+        } else {
+            score
+        };
+        let entry = Entry {
+            key,
+            chess_move,
+            score: score as i16,
+            depth,
+            bound,
+            age,
+        };
+
+        // The previous entry of the position is always replaced, so a key has at most one entry
+        let slot = match (matching, bucket[0]) {
+            (Some(slot), _) => slot,
+            (None, None) => 0,
+            (None, Some(first)) if first.age != age || depth >= first.depth => 0,
+            _ => 1,
+        };
+        bucket[slot] = Some(entry);
+    }
+
+    /// Returns the permille of the entries used by the current search, from a sample of the table.
+    pub fn get_hashfull(&self) -> u16 {
+        let sample = &self.buckets[..self.buckets.len().min(HASHFULL_SAMPLE)];
+        let used = sample.iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.age == self.age)
+            .count();
+        (used * 1000 / (sample.len() * BUCKET_SIZE)) as u16
+    }
+
+    fn index(&self, key: u64) -> usize {
+        key as usize & (self.buckets.len() - 1)
+    }
+}
+
+/// Encodes a move in 16 bits: the destination and the source square (6 bits each), and the
+/// promotion piece (knight, bishop, rook or queen from 1 to 4). The null move is encoded as 0.
+fn encode_move(chess_move: ChessMove) -> u16 {
+    let promotion = match chess_move.get_promotion() {
+        Some(Piece::Knight) => 1,
+        Some(Piece::Bishop) => 2,
+        Some(Piece::Rook) => 3,
+        Some(Piece::Queen) => 4,
+        _ => 0,
+    };
+    chess_move.get_destination().to_index() as u16 | (chess_move.get_source().to_index() as u16) << 6 | promotion << 12
+}
+
+fn decode_move(encoded: u16) -> Option<ChessMove> {
+    let dst = Square::new((encoded & 0x3F) as u8);
+    let src = Square::new(((encoded >> 6) & 0x3F) as u8);
+    let promotion = match (encoded >> 12) & 0x7 {
+        1 => Some(Piece::Knight),
+        2 => Some(Piece::Bishop),
+        3 => Some(Piece::Rook),
+        4 => Some(Piece::Queen),
+        _ => None,
+    };
+
+    match promotion {
+        _ if src == dst => None,
+        Some(piece) => Some(ChessMove::new_promote(src, dst, piece)),
+        None => Some(ChessMove::new(src, dst)),
+    }
+}
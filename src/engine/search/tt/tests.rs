@@ -0,0 +1,102 @@
+use crate::engine::board::chessmove::ChessMove;
+use crate::engine::board::piece::Piece;
+use crate::engine::board::square::Square;
+use crate::engine::search::MATE_SCORE;
+use crate::engine::search::tt::{Bound, TranspositionTable};
+
+fn chess_move(uci: &str) -> ChessMove {
+    ChessMove::new(Square::from_string(&uci[..2]).unwrap(), Square::from_string(&uci[2..]).unwrap())
+}
+
+#[test]
+fn size_is_power_of_two() {
+    let table = TranspositionTable::new(1);
+    assert!(table.len().is_power_of_two());
+    assert!(table.len() * 16 <= 1024 * 1024);
+    assert_eq!(TranspositionTable::new(3).len(), TranspositionTable::new(2).len());
+    assert!(!TranspositionTable::new(0).is_empty());
+}
+
+#[test]
+fn store_and_probe() {
+    let mut table = TranspositionTable::new(1);
+    assert_eq!(None, table.probe(0x1234));
+
+    table.store(0x1234, Some(chess_move("e2e4")), 35, 5, Bound::Exact, 3);
+    let entry = table.probe(0x1234).unwrap();
+    assert_eq!(Some(chess_move("e2e4")), entry.get_move());
+    assert_eq!(35, entry.get_score(7));
+    assert_eq!(5, entry.get_depth());
+    assert_eq!(Bound::Exact, entry.get_bound());
+    assert_eq!(None, table.probe(0x1234 + table.len() as u64));
+
+    let promotion = ChessMove::new_promote(Square::from_string("a7").unwrap(), Square::from_string("b8").unwrap(), Piece::Knight);
+    table.store(0x1234, Some(promotion), -20, 6, Bound::Upper, 3);
+    assert_eq!(Some(promotion), table.probe(0x1234).unwrap().get_move());
+
+    // The best move is kept, if the new result has none
+    table.store(0x1234, None, -50, 7, Bound::Upper, 3);
+    assert_eq!(Some(promotion), table.probe(0x1234).unwrap().get_move());
+    assert_eq!(-50, table.probe(0x1234).unwrap().get_score(3));
+}
+
+#[test]
+fn mate_scores_are_relative_to_the_position() {
+    let mut table = TranspositionTable::new(1);
+    // Mate in 3 plies from the root, found at ply 1
+    table.store(1, None, MATE_SCORE - 3, 2, Bound::Exact, 1);
+    assert_eq!(MATE_SCORE - 3, table.probe(1).unwrap().get_score(1));
+    // The same position reached at ply 3 is mated 2 plies later
+    assert_eq!(MATE_SCORE - 5, table.probe(1).unwrap().get_score(3));
+
+    table.store(2, None, -MATE_SCORE + 4, 2, Bound::Lower, 4);
+    assert_eq!(-MATE_SCORE + 2, table.probe(2).unwrap().get_score(2));
+}
+
+#[test]
+fn replacement() {
+    let mut table = TranspositionTable::new(1);
+    let buckets = table.len() as u64 / 2;
+    let keys = [5, 5 + buckets, 5 + 2 * buckets];
+
+    // The depth-preferred entry keeps the deeper result, the other one is always replaced
+    table.store(keys[0], None, 10, 8, Bound::Exact, 0);
+    table.store(keys[1], None, 20, 4, Bound::Exact, 0);
+    table.store(keys[2], None, 30, 2, Bound::Exact, 0);
+    assert_eq!(8, table.probe(keys[0]).unwrap().get_depth());
+    assert_eq!(None, table.probe(keys[1]));
+    assert_eq!(2, table.probe(keys[2]).unwrap().get_depth());
+
+    table.store(keys[1], None, 20, 9, Bound::Exact, 0);
+    assert_eq!(9, table.probe(keys[1]).unwrap().get_depth());
+    assert_eq!(None, table.probe(keys[0]));
+
+    // The entry of the same position is replaced, even if it is not the depth-preferred one
+    table.store(keys[2], None, 40, 10, Bound::Exact, 0);
+    assert_eq!(10, table.probe(keys[2]).unwrap().get_depth());
+    assert_eq!(9, table.probe(keys[1]).unwrap().get_depth());
+
+    // The results of a previous search are replaced by any result
+    table.new_search();
+    table.store(keys[0], None, 10, 1, Bound::Exact, 0);
+    assert_eq!(1, table.probe(keys[0]).unwrap().get_depth());
+    assert_eq!(table.get_age(), table.probe(keys[0]).unwrap().get_age());
+}
+
+#[test]
+fn clear_and_hashfull() {
+    let mut table = TranspositionTable::new(1);
+    assert_eq!(0, table.get_hashfull());
+
+    for key in 0..table.len() as u64 / 2 {
+        table.store(key, None, 0, 1, Bound::Exact, 0);
+    }
+    assert_eq!(500, table.get_hashfull());
+
+    table.new_search();
+    assert_eq!(0, table.get_hashfull());
+    assert!(table.probe(7).is_some());
+
+    table.clear();
+    assert_eq!(None, table.probe(7));
+}
@@ -8,6 +8,16 @@
 extern crate lazy_static;
 extern crate regex;
 
+use std::env;
+use std::process;
+
 pub mod engine;
+mod cli;
 
-fn main() {}
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(message) = cli::run(&args) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}